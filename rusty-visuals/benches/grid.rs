@@ -0,0 +1,76 @@
+//! Compares building and iterating a Grid against building and iterating the
+//! Vec<Vec<Cell>> that Grid::new used to fill eagerly, for grids the size of
+//! color_interpolation's that are rebuilt in every call to `view`.
+//!
+//! cargo bench --bench grid
+use nannou::prelude::*;
+use rusty_visuals::grid::{Cell, CellIndex, Grid};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 100;
+
+fn bench<F: FnMut() -> f32>(name: &str, mut f: F) -> Duration {
+    // Warm up so that the first iterations don't skew the average.
+    for _ in 0..ITERATIONS / 10 {
+        black_box(f());
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let per_iteration = start.elapsed() / ITERATIONS;
+    println!("{:<40} {:>12?} / iteration", name, per_iteration);
+    per_iteration
+}
+
+// The cells the way Grid::new used to build them: one Vec per row.
+fn eager_cells(bounding_rect: Rect, num_cells: &CellIndex) -> Vec<Vec<Cell>> {
+    let cell_height = bounding_rect.h() / num_cells.row as f32;
+    let cell_width = bounding_rect.w() / num_cells.col as f32;
+    (0..num_cells.row)
+        .map(|row| {
+            (0..num_cells.col)
+                .map(|col| Cell {
+                    wh: vec2(cell_width, cell_height),
+                    xy: vec2(
+                        bounding_rect.top_left().x + (cell_width * (col as f32 + 0.5)),
+                        bounding_rect.top_left().y - (cell_height * (row as f32 + 0.5)),
+                    ),
+                    index: CellIndex { row, col },
+                    span: CellIndex { row: 1, col: 1 },
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn main() {
+    let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 1000.0, 1000.0);
+    for size in [50, 200, 500].iter() {
+        let num_cells = CellIndex {
+            row: *size,
+            col: *size,
+        };
+        println!("{}x{} grid", size, size);
+
+        let lazy = bench("build + iterate (Grid)", || {
+            let grid = Grid::new(rect, &num_cells);
+            grid.row_major_iter()
+                .map(|cell| cell.xy.x + cell.wh.y)
+                .sum()
+        });
+        let eager = bench("build + iterate (Vec<Vec<Cell>>)", || {
+            let cells = eager_cells(rect, &num_cells);
+            cells
+                .iter()
+                .flatten()
+                .map(|cell| cell.xy.x + cell.wh.y)
+                .sum()
+        });
+        println!(
+            "Grid is {:.1}x as fast\n",
+            eager.as_secs_f64() / lazy.as_secs_f64()
+        );
+    }
+}
@@ -13,7 +13,7 @@ fn main() {
 }
 
 struct Model {
-    downsampled_color_map: [[Hsva; GRID_WIDTH]; GRID_HEIGHT],
+    downsampled_color_map: grid::Grid<Hsva>,
 }
 
 fn model(app: &App) -> Model {
@@ -34,15 +34,17 @@ fn model(app: &App) -> Model {
     .into_rgba8();
     let chunk_width = dynamic_image.width() / GRID_WIDTH as u32;
     let chunk_height = dynamic_image.height() / GRID_HEIGHT as u32;
-    let mut downsampled_color_map: [[Hsva; GRID_WIDTH]; GRID_HEIGHT] =
-        [[Hsva::default(); GRID_WIDTH]; GRID_HEIGHT];
-    for row in 0..downsampled_color_map.len() {
-        for col in 0..downsampled_color_map[row].len() {
+    let total_num_cells = grid::CellIndex {
+        row: GRID_HEIGHT,
+        col: GRID_WIDTH,
+    };
+    let downsampled_color_map =
+        grid::Grid::new(app.window_rect(), &total_num_cells).map(|cell, _| {
+            let grid::CellIndex { row, col } = cell.index;
             let averaged_hsv = get_average_rgb(row, col, chunk_width, chunk_height, &dynamic_image);
-            downsampled_color_map[row][col] = averaged_hsv.saturate(0.3);
-            // downsampled_color_map[row][col] = averaged_hsv;
-        }
-    }
+            averaged_hsv.saturate(0.3)
+            // averaged_hsv
+        });
     Model {
         downsampled_color_map,
     }
@@ -93,32 +95,25 @@ fn get_average_rgb(
 
 fn view(app: &App, m: &Model, frame: Frame) {
     let draw = app.draw();
-    let rect = app.window_rect();
 
     if app.elapsed_frames() != 1 {
         return;
     }
     draw.background().color(BLACK);
-    let total_num_cells = grid::CellIndex {
-        row: GRID_HEIGHT,
-        col: GRID_WIDTH,
-    };
-    let grid = grid::Grid::new(&rect, &total_num_cells);
-    for cell in grid.row_major_iter() {
-        let color = m.downsampled_color_map[cell.index.row][cell.index.col];
-
+    for (cell, color) in m.downsampled_color_map.iter() {
         // EXPERIMENT 1: Randomly change cells on the grid to shifted pixels in the
         // original grid
         // EXPERIMENT 2: Use ellipses instead of rectangles to render
         //
         // if random_f32() < 0.3 {
-        //     let shifted_color = m.downsampled_color_map
-        //         [(cell.index.row + 3) % m.downsampled_color_map.len()]
-        //         [(cell.index.col + 3) % m.downsampled_color_map[0].len()];
+        //     let shifted_color = m.downsampled_color_map[grid::CellIndex {
+        //         row: (cell.index.row + 3) % GRID_HEIGHT,
+        //         col: (cell.index.col + 3) % GRID_WIDTH,
+        //     }];
         //     draw.rect().xy(cell.xy).wh(cell.wh).color(shifted_color);
         // } else {
-        draw.ellipse().wh(cell.wh).xy(cell.xy).color(color);
-        // draw.rect().xy(cell.xy).wh(cell.wh).color(color);
+        draw.ellipse().wh(cell.wh).xy(cell.xy).color(*color);
+        // draw.rect().xy(cell.xy).wh(cell.wh).color(*color);
         // }
     }
 
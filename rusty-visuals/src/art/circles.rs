@@ -1,5 +1,6 @@
 use nannou::color::{self, Hsl};
 use nannou::prelude::*;
+use rand::thread_rng;
 use rusty_visuals::*;
 
 const CIRCLE_SIZE: f32 = 10.0;
@@ -55,6 +56,7 @@ fn view(app: &App, _: &Model, frame: Frame) {
         BACKGROUND_LIGHTNESS,
     ));
 
+    let mut rng = thread_rng();
     for _ in 0..NUM_CIRCLES {
         // Draw a bunch of circles with some color
         let position = pt2(
@@ -66,6 +68,7 @@ fn view(app: &App, _: &Model, frame: Frame) {
             CIRCLE_HUE_CENTER + CIRCLE_HUE_WINDOW,
             SATURATION - SATURATION_WINDOW,
             SATURATION + SATURATION_WINDOW,
+            &mut rng,
         );
         draw.ellipse().xy(position).radius(CIRCLE_SIZE).color(hue);
     }
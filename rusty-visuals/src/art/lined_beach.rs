@@ -2,7 +2,7 @@ use nannou::prelude::*;
 use rusty_visuals::*;
 
 fn main() {
-    nannou::app(model).run();
+    nannou::app(model).update(update).run();
 }
 
 struct Model {
@@ -29,6 +29,10 @@ fn model(app: &App) -> Model {
     }
 }
 
+fn update(_app: &App, m: &mut Model, update: Update) {
+    m.colorer.update(&colorer::UpdateTime::from(&update));
+}
+
 fn view(app: &App, m: &Model, frame: Frame) {
     let draw = app.draw();
     let rect = app.window_rect();
@@ -55,7 +59,7 @@ fn draw_sand(draw: &Draw, heights: &Heights, rect: Rect, model: &Model) {
         .align_bottom_of(rect);
 
     let total_num_cells = &grid::CellIndex { row: 10, col: 10 };
-    let grid = grid::Grid::new(&positioning_rect, total_num_cells);
+    let grid = grid::Grid::new(positioning_rect, total_num_cells);
     grid.row_major_iter().for_each(|cell| {
         draw.rect()
             .xy(cell.xy)
@@ -61,14 +61,9 @@ fn model(app: &App) -> Model {
     //     hsv(17.0 / 360.0, 0.20, 0.27),
     // ];
 
-    // https://colorpalettes.net/color-palette-4291/
-    let color_scheme = vec![
-        hsv(39.0 / 360.0, 0.760, 0.980),
-        hsv(38.0 / 360.0, 0.350, 0.98),
-        hsv(116.0 / 360.0, 0.070, 0.780),
-        hsv(96.0 / 360.0, 0.310, 0.40),
-        hsv(84.0 / 360.0, 0.39, 0.20),
-    ];
+    let color_scheme = palettes::Palette::load("src/art/resources/palettes/colorpalettes-4291.hex")
+        .unwrap()
+        .colors;
 
     let circle_radius_start = pt2(0.0, 0.0);
 
@@ -1,7 +1,12 @@
 use nannou::color::*;
 use nannou::prelude::*;
-use rusty_visuals::colorer::{GridColorer, GridParams, InterpolatedColorer};
+use rusty_visuals::colorer::{
+    ContextColorer, GradientColorer, GradientMapping, GridColorer, GridParams, InterpolatedColorer,
+    UpdateTime,
+};
+use rusty_visuals::gradient::{ColorGradient, Easing};
 use rusty_visuals::grid::CellIndex;
+use rusty_visuals::spaces::Interpolation;
 use rusty_visuals::*;
 use std::collections::VecDeque;
 
@@ -31,7 +36,24 @@ fn model(app: &App) -> Model {
             Hsv::new(60.0, 1.0, 1.0),
             Hsv::new(180.0, 1.0, 1.0),
         ))),
+        // Crosses the red end of the hue wheel without passing through green.
+        Box::new(
+            InterpolatedColorer::new((Hsv::new(300.0, 0.8, 0.9), Hsv::new(60.0, 0.8, 0.9)))
+                .interpolation(Interpolation::Oklch),
+        ),
         Box::new(sun_and_sky_colorer),
+        Box::new(GradientColorer::new(
+            ColorGradient::from_stops(vec![
+                (0.0, Hsv::new(50.0, 0.6, 1.0)),
+                (0.3, Hsv::new(20.0, 0.9, 0.95)),
+                (1.0, Hsv::new(250.0, 0.8, 0.3)),
+            ])
+            .interpolation(Interpolation::Oklch)
+            .segment_easing(1, Easing::EaseOut),
+            GradientMapping::Radial {
+                center: vec2(0.5, 0.3),
+            },
+        )),
     ];
     let colorers_vec_deque = VecDeque::from(colorers);
     let colorer = colorer::RotatingColorer::new(colorers_vec_deque);
@@ -47,7 +69,7 @@ fn view(app: &App, m: &Model, frame: Frame) {
     draw.background().color(WHITE);
 
     let num_cells = CellIndex { row: 200, col: 200 };
-    let grid = grid::Grid::new(&rect, &num_cells);
+    let grid = grid::Grid::new(rect, &num_cells);
     for cell in grid.row_major_iter() {
         draw.rect()
             .xy(cell.xy)
@@ -71,7 +93,7 @@ fn event(app: &App, m: &mut Model, event: WindowEvent) {
             println!("Mouse Position: {}, {}", app.mouse.y, app.mouse.x);
         }
         KeyPressed(Key::C) => {
-            m.colorer.update();
+            m.colorer.update(&UpdateTime::from_app(app));
         }
         _other => (),
     }
@@ -82,8 +104,8 @@ struct SunAndSky {
     grid_rect: Rect,
 }
 
-impl GridColorer for SunAndSky {
-    fn color(&self, params: GridParams) -> Hsv {
+impl<'a> ContextColorer<GridParams<'a>> for SunAndSky {
+    fn color(&self, params: GridParams<'a>) -> Hsv {
         let radius = self.grid_rect.w();
         // Colors in a circle
         if (params.cell.left() + (radius / 2.0)).pow(2.0)
@@ -97,7 +119,7 @@ impl GridColorer for SunAndSky {
         Hsv::new(0.0, 0.0, 1.0)
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, _: &UpdateTime) {}
 }
 
 impl SunAndSky {
@@ -1,10 +1,12 @@
 use nannou::color::*;
 use nannou::prelude::*;
 use rusty_visuals::colorer::{
-    AlternatingColorer, GridColorer, GridParams, PaletteColorer, RotatingColorer,
+    AlternatingColorer, BlendColorer, GridColorer, GridParams, MaskColorer, OffsetColorer,
+    PaletteColorer, PastelColorer, RotatingColorer, UpdateTime,
 };
 use rusty_visuals::file_utils;
 use rusty_visuals::grid::{CellIndex, Grid};
+use rusty_visuals::harmony::{Harmony, HarmonyBuilder};
 use std::collections::VecDeque;
 
 fn main() {
@@ -35,10 +37,31 @@ fn model(app: &App) -> Model {
     );
     let alternating_colorer_2 =
         AlternatingColorer::new(vec![hsv(0.2, 0.5, 1.0), hsv(0.7, 0.5, 1.0)]);
+    let split_complementary = AlternatingColorer::from_palette(
+        &HarmonyBuilder::new(Harmony::SplitComplementary, hsv(0.6, 0.5, 0.9))
+            .shades(2)
+            .build(),
+    );
+    // Every third diagonal in dark pastels, and everything else in greens tinted with pink.
+    let diagonals = MaskColorer::new(
+        |params: GridParams| (params.cell.index.row + params.cell.index.col).is_multiple_of(3),
+        Box::new(OffsetColorer::new(Box::new(PastelColorer::new())).value_offset(-0.4)),
+        Box::new(BlendColorer::new(
+            Box::new(PaletteColorer::new(
+                (90..180).map(|n| n as f32 / 360.0).collect(),
+                vec![0.6],
+                vec![0.8],
+            )),
+            Box::new(AlternatingColorer::new(vec![hsv(0.9, 0.6, 1.0)])),
+            0.3,
+        )),
+    );
     let colorers: Vec<Box<dyn GridColorer>> = vec![
         Box::new(pastels),
         Box::new(alternating_colorer_2),
         Box::new(greens),
+        Box::new(split_complementary),
+        Box::new(diagonals),
     ];
     let colorers_vec_deque = VecDeque::from(colorers);
     let colorer = RotatingColorer::new(colorers_vec_deque);
@@ -67,7 +90,7 @@ fn view(app: &App, m: &Model, frame: Frame) {
 
 fn draw_grid_with_transperancy(draw: &Draw, rect: &Rect, m: &Model) {
     let num_cells = CellIndex { row: 20, col: 20 };
-    let grid = Grid::new(&rect, &num_cells);
+    let grid = Grid::new(*rect, &num_cells);
     for cell in grid.row_major_iter() {
         if random_f32() < 0.4 {
             continue;
@@ -98,7 +121,7 @@ fn draw_grid_with_transperancy(draw: &Draw, rect: &Rect, m: &Model) {
 
 fn draw_basic_grid(draw: &Draw, rect: &Rect, m: &Model) {
     let num_cells = CellIndex { row: 30, col: 10 };
-    let grid = Grid::new(&rect, &num_cells);
+    let grid = Grid::new(*rect, &num_cells);
     for cell in grid.row_major_iter() {
         draw.rect()
             .xy(cell.xy)
@@ -117,7 +140,7 @@ fn event(app: &App, m: &mut Model, event: WindowEvent) {
             file_utils::capture_frame_to_path(app);
         }
         KeyPressed(Key::C) => {
-            m.colorer.update();
+            m.colorer.update(&UpdateTime::from_app(app));
             m.to_update_on_frames = app.elapsed_frames() + 1;
         }
         _other => (),
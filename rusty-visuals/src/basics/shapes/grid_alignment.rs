@@ -2,10 +2,9 @@ use chrono::Local;
 use geo::Line;
 use line_intersection::LineInterval;
 use nannou::prelude::*;
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
 use rusty_visuals::grid::*;
 use rusty_visuals::hi_res_capture::HiResCapture;
+use rusty_visuals::truchet::{Truchet, TruchetStyle, TruchetTile};
 
 fn main() {
     // The `exit` handler makes sure the PNG is captured before exiting
@@ -88,24 +87,10 @@ fn drawing_0(app: &App, model: &mut Model) {
     let draw = &model.hi_res_capture.draw;
     draw.background().color(WHITE);
 
-    // https://rust-random.github.io/book/guide-start.html
-    let mut rng = StdRng::seed_from_u64(model.seed);
-    for cell in model.grid.row_major_iter() {
-        let line_direction = rng.gen_range(0.0, 2.0).floor() as i32;
-        if line_direction == 0 {
-            draw.line()
-                .caps_round()
-                .start(pt2(cell.left(), cell.top()))
-                .end(pt2(cell.right(), cell.bottom()))
-                .weight(40.0);
-        } else {
-            draw.line()
-                .caps_round()
-                .start(pt2(cell.left(), cell.bottom()))
-                .end(pt2(cell.right(), cell.top()))
-                .weight(40.0);
-        }
-    }
+    let truchet = Truchet::builder(TruchetTile::Diagonal)
+        .seed(model.seed)
+        .build(&model.grid);
+    truchet.draw(draw, &TruchetStyle::default().line_weight(40.0));
 
     model.hi_res_capture.update(app, model.save_image);
 }
@@ -115,42 +100,14 @@ fn drawing_1(app: &App, model: &mut Model) {
     let draw = &model.hi_res_capture.draw;
     draw.background().color(WHITE);
 
-    // https://rust-random.github.io/book/guide-start.html
-    let mut rng = StdRng::seed_from_u64(model.seed);
-    for cell in model.grid.row_major_iter() {
-        let line_direction = rng.gen_range(0.0, 2.0).floor() as i32;
-        let front_color = Hsv::new(0.0, 0.0, 0.0);
-        let back_color = Hsva::new(0.0, 0.0, 0.1, 0.7);
-        if line_direction == 0 {
-            draw.line()
-                .caps_round()
-                .start(pt2(cell.left(), cell.top()))
-                .end(pt2(cell.right(), cell.bottom()))
-                .weight(80.0)
-                .join_miter()
-                .color(back_color);
-            draw.line()
-                .start(pt2(cell.left(), cell.top()))
-                .end(pt2(cell.right(), cell.bottom()))
-                .weight(40.0)
-                .color(front_color)
-                .caps_round();
-        } else {
-            draw.line()
-                .caps_round()
-                .start(pt2(cell.left(), cell.bottom()))
-                .end(pt2(cell.right(), cell.top()))
-                .weight(80.0)
-                .join_miter()
-                .color(back_color);
-            draw.line()
-                .start(pt2(cell.left(), cell.bottom()))
-                .end(pt2(cell.right(), cell.top()))
-                .weight(40.0)
-                .color(front_color)
-                .caps_round();
-        }
-    }
+    let truchet = Truchet::builder(TruchetTile::Diagonal)
+        .seed(model.seed)
+        .build(&model.grid);
+    let back_style = TruchetStyle::default()
+        .foreground(Hsva::new(0.0, 0.0, 0.1, 0.7))
+        .line_weight(80.0);
+    truchet.draw(draw, &back_style);
+    truchet.draw(draw, &TruchetStyle::default().line_weight(40.0));
 
     model.hi_res_capture.update(app, model.save_image);
 }
@@ -16,7 +16,6 @@ const GRID_ROW_RESOLUTION: usize = 15;
 
 struct Model {
     frame: Rect,
-    colorer: Box<dyn Colorer>,
     hi_res_capture: HiResCapture,
     // save_image tells the `update` handler to write the
     // texture to PNG
@@ -42,12 +41,8 @@ fn model(app: &App) -> Model {
     let frame = geom::Rect::from_w_h((width - 200) as f32, (height - 200) as f32);
     
     
-    let pastel_colorer = PastelColorer::new();
-    let colorer = ModuloColorer::new(Box::new(pastel_colorer), Hsv::new(0.0, 0.0, 1.0), 3);
-
     Model {
         frame,
-        colorer: Box::new(colorer),
         hi_res_capture,
         save_image: false,
         seed: Local::now().timestamp() as u64,
@@ -122,6 +117,10 @@ fn drawing_1(app: &App, model: &mut Model) {
     let draw = &model.hi_res_capture.draw;
     draw.background().color(WHITE);
 
+    // The colorer is rebuilt from the seed on every render so that every
+    // frame (and the captured image) gets the same colors.
+    let pastel_colorer = PastelColorer::seeded(model.seed);
+    let colorer = ModuloColorer::new(Box::new(pastel_colorer), Hsv::new(0.0, 0.0, 1.0), 3);
     let mut rng = StdRng::seed_from_u64(model.seed);
     let rotation_angle = 2.0;
     let mut start_angle = 0.0;
@@ -131,7 +130,7 @@ fn drawing_1(app: &App, model: &mut Model) {
     while start_angle <= 360.0 {
         let w = wh.x - map_range(start_angle, 0.0, 360.0, 10.0, wh.x);
         let h = wh.y - map_range(start_angle, 0.0, 360.0, 10.0, wh.y);
-        let color = model.colorer.color();
+        let color = colorer.color(());
         draw.rect()
             .xy(model.frame.xy())
             .wh(vec2(w, h))
@@ -1,22 +1,27 @@
 use nannou::prelude::*;
-use crate::colorer::{GridColorer, GridParams};
+use crate::colorer::{ContextColorer, GridParams, UpdateTime};
+use crate::palettes::Palette;
 
 pub struct AlternatingColorer {
     colors: Vec<Hsv>,
 }
 
-impl GridColorer for AlternatingColorer {
-    fn color(&self, params: GridParams) -> Hsv {
+impl<'a> ContextColorer<GridParams<'a>> for AlternatingColorer {
+    fn color(&self, params: GridParams<'a>) -> Hsv {
         let position =
             ((params.cell.index.col + params.cell.index.row) as i32) % self.colors.len() as i32;
         *self.colors.get(position as usize).unwrap()
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, _: &UpdateTime) {}
 }
 
 impl AlternatingColorer {
     pub fn new(colors: Vec<Hsv>) -> Self {
         AlternatingColorer { colors }
     }
+
+    pub fn from_palette(palette: &Palette) -> Self {
+        AlternatingColorer::new(palette.colors.clone())
+    }
 }
@@ -0,0 +1,75 @@
+use crate::colorer::{ContextColorer, UpdateTime};
+use crate::spaces::Interpolation;
+use nannou::prelude::*;
+
+/// BlendColorer mixes the colors of two colorers for the same context. An amount of 0 gives the
+/// first colorer's color and 1 the second's. Colors are blended in HSV unless another
+/// Interpolation is set.
+pub struct BlendColorer<A: ?Sized, B: ?Sized> {
+    first: Box<A>,
+    second: Box<B>,
+    amount: f32,
+    interpolation: Interpolation,
+}
+
+impl<C: Copy, A, B> ContextColorer<C> for BlendColorer<A, B>
+where
+    A: ContextColorer<C> + ?Sized,
+    B: ContextColorer<C> + ?Sized,
+{
+    fn color(&self, context: C) -> Hsv {
+        self.interpolation.mix(
+            (*self.first).color(context),
+            (*self.second).color(context),
+            self.amount,
+        )
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        (*self.first).update(time);
+        (*self.second).update(time);
+    }
+}
+
+impl<A: ?Sized, B: ?Sized> BlendColorer<A, B> {
+    pub fn new(first: Box<A>, second: Box<B>, amount: f32) -> Self {
+        BlendColorer {
+            first,
+            second,
+            amount,
+            interpolation: Interpolation::Hsv,
+        }
+    }
+
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlendColorer;
+    use crate::colorer::{Colorer, ContextColorer, UpdateTime};
+    use nannou::prelude::*;
+
+    struct Constant(Hsv);
+
+    impl Colorer for Constant {
+        fn color(&self) -> Hsv {
+            self.0
+        }
+
+        fn update(&mut self, _: &UpdateTime) {}
+    }
+
+    #[test]
+    fn blends_the_colors() {
+        let blend = BlendColorer::new(
+            Box::new(Constant(Hsv::new(0.0, 1.0, 0.2))),
+            Box::new(Constant(Hsv::new(0.0, 1.0, 0.6))),
+            0.25,
+        );
+        assert!((blend.color(()).value - 0.3).abs() < 1e-5);
+    }
+}
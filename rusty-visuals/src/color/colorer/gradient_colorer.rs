@@ -0,0 +1,124 @@
+use crate::colorer::{ContextColorer, GridParams, UpdateTime};
+use crate::gradient::ColorGradient;
+use nannou::prelude::*;
+
+/// GradientMapping places every grid cell somewhere along a gradient. Positions are worked out
+/// in the grid's unit square, with (0, 0) at the bottom left and (1, 1) at the top right, so
+/// radial and angular gradients are stretched along with the grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientMapping {
+    /// From the first column to the last one.
+    Horizontal,
+    /// From the first (top) row to the last one.
+    Vertical,
+    /// Across the whole grid in a direction, in radians counter clockwise from left to right.
+    Linear { angle: f32 },
+    /// Outwards from a center to the grid corner furthest from it.
+    Radial { center: Vec2 },
+    /// Counter clockwise around a center, starting to its right.
+    Angular { center: Vec2 },
+}
+
+/// GradientColorer colors every cell with the color of its position along a ColorGradient.
+pub struct GradientColorer {
+    gradient: ColorGradient,
+    mapping: GradientMapping,
+}
+
+impl<'a> ContextColorer<GridParams<'a>> for GradientColorer {
+    fn color(&self, params: GridParams<'a>) -> Hsv {
+        self.gradient.get(self.position(params))
+    }
+
+    fn update(&mut self, _: &UpdateTime) {}
+}
+
+impl GradientColorer {
+    pub fn new(gradient: ColorGradient, mapping: GradientMapping) -> Self {
+        GradientColorer { gradient, mapping }
+    }
+
+    // The cell's position along the gradient, from 0 to 1.
+    fn position(&self, params: GridParams) -> f32 {
+        let index = params.cell.index;
+        let num_cells = params.total_num_cells;
+        // The cell's center in the grid's unit square.
+        let uv = vec2(
+            (index.col as f32 + 0.5) / num_cells.col as f32,
+            1.0 - (index.row as f32 + 0.5) / num_cells.row as f32,
+        );
+        let along = |idx: usize, len: usize| {
+            if len > 1 {
+                idx as f32 / (len - 1) as f32
+            } else {
+                0.0
+            }
+        };
+        match self.mapping {
+            GradientMapping::Horizontal => along(index.col, num_cells.col),
+            GradientMapping::Vertical => along(index.row, num_cells.row),
+            GradientMapping::Linear { angle } => {
+                let direction = vec2(angle.cos(), angle.sin());
+                // Half the length of the unit square's shadow along the direction.
+                let extent = 0.5 * (direction.x.abs() + direction.y.abs());
+                ((uv - vec2(0.5, 0.5)).dot(direction) + extent) / (2.0 * extent)
+            }
+            GradientMapping::Radial { center } => {
+                let furthest_corner =
+                    vec2(center.x.max(1.0 - center.x), center.y.max(1.0 - center.y));
+                (uv - center).length() / furthest_corner.length()
+            }
+            GradientMapping::Angular { center } => {
+                let offset = uv - center;
+                offset.y.atan2(offset.x).rem_euclid(TAU) / TAU
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GradientColorer, GradientMapping};
+    use crate::colorer::{ContextColorer, GridParams};
+    use crate::gradient::ColorGradient;
+    use crate::grid::{CellIndex, Grid};
+    use nannou::prelude::*;
+
+    #[test]
+    fn maps_cells_along_the_gradient() {
+        let num_cells = CellIndex { row: 3, col: 5 };
+        let grid = Grid::new(Rect::from_w_h(100.0, 60.0), &num_cells);
+        let gradient = ColorGradient::new(vec![Hsv::new(0.0, 1.0, 0.0), Hsv::new(0.0, 1.0, 1.0)]);
+        let values = |mapping: GradientMapping| {
+            let colorer = GradientColorer::new(gradient.clone(), mapping);
+            grid.row_major_iter()
+                .map(|cell| {
+                    colorer
+                        .color(GridParams {
+                            cell: &cell,
+                            total_num_cells: &num_cells,
+                        })
+                        .value
+                })
+                .collect::<Vec<f32>>()
+        };
+
+        let horizontal = values(GradientMapping::Horizontal);
+        assert_eq!(horizontal[0], 0.0);
+        assert_eq!(horizontal[4], 1.0);
+        assert!((horizontal[2] - 0.5).abs() < 1e-5);
+
+        // The center cell is the center of a radial gradient.
+        let radial = values(GradientMapping::Radial {
+            center: vec2(0.5, 0.5),
+        });
+        assert_eq!(radial[7], 0.0);
+        assert!(radial.iter().all(|value| *value < 1.0));
+
+        // Right of the center is where an angular gradient starts.
+        let angular = values(GradientMapping::Angular {
+            center: vec2(0.5, 0.5),
+        });
+        assert!(angular[9] < 1e-5);
+    }
+}
@@ -1,19 +1,17 @@
-use nannou::color::*;
+use crate::colorer::ContextColorer;
 use crate::grid;
 
 /// GridParams are all the various options provided to a Colorer's
-/// `color` function
-/// TODO: This should be generic so that the Colorer can color other things
-/// and not just grids.
+/// `color` function when coloring a grid cell.
+#[derive(Clone, Copy)]
 pub struct GridParams<'a> {
     pub cell: &'a grid::Cell,
     pub total_num_cells: &'a grid::CellIndex,
 }
 
-/// GridColorer is the trait that all GridColorer's must implement. As long as a struct
-/// implements this trait, it can be used to color a ColoredGrid.
-pub trait GridColorer {
-    fn color(&self, params: GridParams) -> Hsv;
+/// GridColorer is any colorer that can color grid cells. As long as a struct
+/// implements ContextColorer<GridParams>, it is a GridColorer and can be used
+/// to color a ColoredGrid.
+pub trait GridColorer: for<'a> ContextColorer<GridParams<'a>> {}
 
-    fn update(&mut self);
-}
+impl<T> GridColorer for T where T: for<'a> ContextColorer<GridParams<'a>> {}
@@ -1,20 +1,29 @@
+use crate::colorer::{ContextColorer, GridParams, UpdateTime};
+use crate::spaces::Interpolation;
 use nannou::prelude::*;
-use nannou::color::*;
-use crate::colorer::{GridColorer, GridParams};
+use std::cell::RefCell;
 
-// mod grid_colorer;
-// use grid_colorer::{GridColorer, GridParams};
-
-/// InterpolatedColorer will color the grid's first row with the provided Gradient.
+/// InterpolatedColorer will color the grid's first row with a gradient between the two colors.
 /// All subsequent rows are colored starting with the color pointed to by the current row's index
-/// into the Gradient. The end of the Gradient is shifted by 30 degrees.
+/// into that gradient, and end as far from their start as the second color is from the first.
+/// Colors are blended in HSV unless another Interpolation is set.
 pub struct InterpolatedColorer {
-    base_gradient: Gradient<Hsv>,
+    from: Hsv,
+    to: Hsv,
+    interpolation: Interpolation,
+    // The start and end of every row's gradient, in the interpolation's color space. They are
+    // computed on the first color of a grid and again only when the number of rows changes.
+    rows: RefCell<Vec<([f32; 3], [f32; 3])>>,
 }
 
-impl GridColorer for InterpolatedColorer {
-    fn color(&self, params: GridParams) -> Hsv {
-        // Choose the color on the x gradient plane
+impl<'a> ContextColorer<GridParams<'a>> for InterpolatedColorer {
+    fn color(&self, params: GridParams<'a>) -> Hsv {
+        let mut rows = self.rows.borrow_mut();
+        if rows.len() != params.total_num_cells.row {
+            *rows = self.row_gradients(params.total_num_cells.row);
+        }
+        // Choose the color on the x gradient plane of the cell's row.
+        let (start, end) = rows[params.cell.index.row];
         let color_for_idx = map_range(
             params.cell.index.col,
             0,
@@ -22,32 +31,82 @@ impl GridColorer for InterpolatedColorer {
             0.0,
             1.0,
         );
-        // Create the plane for the y axis and then select the
-        // x color plane from this gradient.
-        self.get_gradient(params.cell.index.row, params.total_num_cells.row)
-            .get(color_for_idx)
+        self.interpolation
+            .color_at(self.interpolation.lerp(start, end, color_for_idx))
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, _: &UpdateTime) {}
 }
 
 impl InterpolatedColorer {
     pub fn new(color_range: (Hsv, Hsv)) -> Self {
-        let base_gradient = Gradient::new(vec![color_range.0, color_range.1]);
-        InterpolatedColorer { base_gradient }
+        InterpolatedColorer {
+            from: color_range.0,
+            to: color_range.1,
+            interpolation: Interpolation::Hsv,
+            rows: RefCell::new(vec![]),
+        }
+    }
+
+    /// The color space the colors are blended in. Interpolation::Oklch avoids the muddy
+    /// midpoints of HSV blends.
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self.rows = RefCell::new(vec![]);
+        self
     }
 
-    // TODO: This can be precomputed if we know the number of tiles in the grid when the
-    // interpolated colorer is constructed.
-    fn get_gradient(&self, row: usize, num_rows: usize) -> Gradient<Hsv> {
-        let y_gradient_start_idx = map_range(row, 0, num_rows, 0.0, 1.0);
-        let y_gradient_start = self.base_gradient.get(y_gradient_start_idx);
+    fn row_gradients(&self, num_rows: usize) -> Vec<([f32; 3], [f32; 3])> {
+        let from = self.interpolation.coords_of(self.from);
+        let to = self.interpolation.coords_of(self.to);
+        // Keep the difference between every row's start and end the same as the first row's.
+        let original_difference = self.interpolation.difference(from, to);
+        (0..num_rows)
+            .map(|row| {
+                let y_gradient_start_idx = map_range(row, 0, num_rows, 0.0, 1.0);
+                let y_gradient_start = self.interpolation.lerp(from, to, y_gradient_start_idx);
+                let y_gradient_end = [
+                    y_gradient_start[0] + original_difference[0],
+                    y_gradient_start[1] + original_difference[1],
+                    y_gradient_start[2] + original_difference[2],
+                ];
+                (y_gradient_start, y_gradient_end)
+            })
+            .collect()
+    }
+}
 
-        // Keep the difference between the new start and end the same by using the original
-        // gradient's difference
-        let original_difference = self.base_gradient.get(1.0) - self.base_gradient.get(0.0);
-        let y_gradient_end = y_gradient_start + original_difference;
+#[cfg(test)]
+mod tests {
+    use super::InterpolatedColorer;
+    use crate::colorer::{ContextColorer, GridParams};
+    use crate::grid::{CellIndex, Grid};
+    use crate::spaces::Interpolation;
+    use nannou::prelude::*;
 
-        Gradient::new(vec![y_gradient_start, y_gradient_end])
+    #[test]
+    fn blends_across_the_hue_seam() {
+        let num_cells = CellIndex { row: 2, col: 4 };
+        let grid = Grid::new(Rect::from_w_h(100.0, 100.0), &num_cells);
+        for interpolation in [Interpolation::Hsv, Interpolation::Oklch].iter() {
+            let colorer =
+                InterpolatedColorer::new((Hsv::new(340.0, 1.0, 1.0), Hsv::new(20.0, 1.0, 1.0)))
+                    .interpolation(*interpolation);
+            for cell in grid.row_major_iter() {
+                let color = colorer.color(GridParams {
+                    cell: &cell,
+                    total_num_cells: &num_cells,
+                });
+                // The short way from magenta-red to orange-red passes through red, never green
+                // or blue.
+                let hue = color.hue.to_positive_degrees();
+                assert!(
+                    !(60.0..=300.0).contains(&hue),
+                    "{:?}: {}",
+                    interpolation,
+                    hue
+                );
+            }
+        }
     }
 }
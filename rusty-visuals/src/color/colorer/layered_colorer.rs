@@ -0,0 +1,87 @@
+use crate::colorer::{ContextColorer, UpdateTime};
+use crate::spaces::Interpolation;
+use nannou::prelude::*;
+
+/// LayeredColorer paints layers of colorers over a base colorer, from the first layer added to
+/// the last. Every layer covers what is under it by its opacity, from 0 (invisible) to 1
+/// (covers it completely). Colors are blended in HSV unless another Interpolation is set.
+/// The base can be any colorer of the same context as the layers, and layers of different
+/// kinds can be mixed through a trait object (ex. `Box<dyn GridColorer>`).
+pub struct LayeredColorer<B: ?Sized, K: ?Sized> {
+    base: Box<B>,
+    layers: Vec<(Box<K>, f32)>,
+    interpolation: Interpolation,
+}
+
+impl<C: Copy, B, K> ContextColorer<C> for LayeredColorer<B, K>
+where
+    B: ContextColorer<C> + ?Sized,
+    K: ContextColorer<C> + ?Sized,
+{
+    fn color(&self, context: C) -> Hsv {
+        self.layers
+            .iter()
+            .fold((*self.base).color(context), |color, (layer, opacity)| {
+                self.interpolation
+                    .mix(color, (**layer).color(context), *opacity)
+            })
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        (*self.base).update(time);
+        for (layer, _) in self.layers.iter_mut() {
+            (**layer).update(time);
+        }
+    }
+}
+
+impl<B: ?Sized, K: ?Sized> LayeredColorer<B, K> {
+    pub fn new(base: Box<B>) -> Self {
+        LayeredColorer {
+            base,
+            layers: vec![],
+            interpolation: Interpolation::Hsv,
+        }
+    }
+
+    pub fn layer(mut self, colorer: Box<K>, opacity: f32) -> Self {
+        self.layers.push((colorer, opacity));
+        self
+    }
+
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayeredColorer;
+    use crate::colorer::{AlternatingColorer, ContextColorer, GridColorer, GridParams};
+    use crate::grid::{CellIndex, Grid};
+    use crate::spaces::Interpolation;
+    use nannou::prelude::*;
+
+    #[test]
+    fn layers_cover_the_base_by_their_opacity() {
+        let red = Hsv::new(0.0, 1.0, 1.0);
+        let blue = Hsv::new(240.0, 1.0, 1.0);
+        let num_cells = CellIndex { row: 1, col: 1 };
+        let grid = Grid::new(Rect::from_w_h(10.0, 10.0), &num_cells);
+        let params = GridParams {
+            cell: &grid.get(&CellIndex { row: 0, col: 0 }).unwrap(),
+            total_num_cells: &num_cells,
+        };
+        let layered = |opacity: f32| {
+            let blue: Box<dyn GridColorer> = Box::new(AlternatingColorer::new(vec![blue]));
+            LayeredColorer::new(Box::new(AlternatingColorer::new(vec![red]))).layer(blue, opacity)
+        };
+        assert_eq!(layered(0.0).color(params), red);
+        assert_eq!(layered(1.0).color(params), blue);
+        assert_eq!(
+            layered(0.5).color(params),
+            Interpolation::Hsv.mix(red, blue, 0.5)
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::colorer::{ContextColorer, UpdateTime};
+use nannou::prelude::*;
+
+/// MaskColorer colors with one colorer wherever the mask is true and with another everywhere
+/// else. The mask looks at the same context as the colorers, ex.
+/// `MaskColorer::new(|params: GridParams| params.cell.index.row % 2 == 0, stripes, background)`.
+pub struct MaskColorer<F, A: ?Sized, B: ?Sized> {
+    mask: F,
+    inside: Box<A>,
+    outside: Box<B>,
+}
+
+impl<C: Copy, F, A, B> ContextColorer<C> for MaskColorer<F, A, B>
+where
+    F: Fn(C) -> bool,
+    A: ContextColorer<C> + ?Sized,
+    B: ContextColorer<C> + ?Sized,
+{
+    fn color(&self, context: C) -> Hsv {
+        if (self.mask)(context) {
+            (*self.inside).color(context)
+        } else {
+            (*self.outside).color(context)
+        }
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        (*self.inside).update(time);
+        (*self.outside).update(time);
+    }
+}
+
+impl<F, A: ?Sized, B: ?Sized> MaskColorer<F, A, B> {
+    pub fn new(mask: F, inside: Box<A>, outside: Box<B>) -> Self {
+        MaskColorer {
+            mask,
+            inside,
+            outside,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaskColorer;
+    use crate::colorer::{AlternatingColorer, GridColorer, GridParams};
+    use crate::grid::{CellIndex, Grid};
+    use nannou::prelude::*;
+
+    #[test]
+    fn masks_cells() {
+        let red = Hsv::new(0.0, 1.0, 1.0);
+        let blue = Hsv::new(240.0, 1.0, 1.0);
+        let colorer: Box<dyn GridColorer> = Box::new(MaskColorer::new(
+            |params: GridParams| params.cell.index.col == 0,
+            Box::new(AlternatingColorer::new(vec![red])),
+            Box::new(AlternatingColorer::new(vec![blue])),
+        ));
+        let num_cells = CellIndex { row: 2, col: 3 };
+        let grid = Grid::new(Rect::from_w_h(30.0, 20.0), &num_cells);
+        for cell in grid.row_major_iter() {
+            let color = colorer.color(GridParams {
+                cell: &cell,
+                total_num_cells: &num_cells,
+            });
+            let expected = if cell.index.col == 0 { red } else { blue };
+            assert_eq!(color, expected);
+        }
+    }
+}
@@ -4,26 +4,145 @@ pub use grid_colorer::*;
 mod alternating_colorer;
 pub use alternating_colorer::*;
 
+mod blend_colorer;
+pub use blend_colorer::*;
+
+mod gradient_colorer;
+pub use gradient_colorer::*;
+
 mod interpolated_colorer;
 pub use interpolated_colorer::*;
 
+mod layered_colorer;
+pub use layered_colorer::*;
+
+mod mask_colorer;
+pub use mask_colorer::*;
+
 mod modulo_colorer;
 pub use modulo_colorer::*;
 
+mod noise_colorer;
+pub use noise_colorer::*;
+
+mod offset_colorer;
+pub use offset_colorer::*;
+
 mod palette_colorer;
 pub use palette_colorer::*;
 
 mod rotating_colorer;
 pub use rotating_colorer::*;
 
+mod weighted_colorer;
+pub use weighted_colorer::*;
+
 use nannou::color::*;
+use nannou::event::Update;
+use nannou::App;
+use std::time::Duration;
+
+/// UpdateTime is how much time has passed when a colorer is updated, so that animated colorers
+/// change at the same speed whatever the frame rate. In an update function, build it from
+/// nannou's Update with `UpdateTime::from(&update)`. Outside of one (ex. on a key press), use
+/// `UpdateTime::from_app(app)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UpdateTime {
+    /// Time since the app started.
+    pub since_start: Duration,
+    /// Time since the previous update.
+    pub since_last: Duration,
+}
+
+impl From<&Update> for UpdateTime {
+    fn from(update: &Update) -> Self {
+        UpdateTime {
+            since_start: update.since_start,
+            since_last: update.since_last,
+        }
+    }
+}
+
+impl UpdateTime {
+    pub fn new(since_start: Duration, since_last: Duration) -> Self {
+        UpdateTime {
+            since_start,
+            since_last,
+        }
+    }
+
+    /// The time of the app's latest update.
+    pub fn from_app(app: &App) -> Self {
+        UpdateTime {
+            since_start: app.duration.since_start,
+            since_last: app.duration.since_prev_update,
+        }
+    }
+}
 
-/// Colorer is a more generic version of the GridColorer. The GridColorer is expected to color
-/// grids, however this Colorer is more stateless in that there are no arguments being
-/// fed to it.
-/// TODO: I wonder if we could use generics to implement a GridColorer using the Colorer 
+/// ContextColorer is the trait every colorer implements. `C` is whatever is
+/// being colored and what the colorer may look at to pick the color: a grid
+/// cell (GridParams), a particle, a position along a path, a pixel...
+/// Colorers that don't care (ex. RotatingColorer) implement it for every `C`.
+pub trait ContextColorer<C> {
+    fn color(&self, context: C) -> Hsv;
+
+    /// Moves animated colorers forward in time. Colorers that don't change over time do
+    /// nothing.
+    fn update(&mut self, time: &UpdateTime);
+}
+
+/// Colorer is a colorer that needs no context to pick a color. Every Colorer
+/// is also a ContextColorer for any context, so it can be used wherever a
+/// GridColorer or any other ContextColorer is expected.
 pub trait Colorer {
     fn color(&self) -> Hsv;
 
-    fn update(&mut self);
+    fn update(&mut self, time: &UpdateTime);
+}
+
+impl<C, T: Colorer + ?Sized> ContextColorer<C> for T {
+    fn color(&self, _: C) -> Hsv {
+        Colorer::color(self)
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        Colorer::update(self, time)
+    }
+}
+
+// Always gives the same color, for the tests of the colorers that are built
+// on top of other colorers.
+#[cfg(test)]
+pub(crate) struct SolidColorer(pub Hsv);
+
+#[cfg(test)]
+impl Colorer for SolidColorer {
+    fn color(&self) -> Hsv {
+        self.0
+    }
+
+    fn update(&mut self, _: &UpdateTime) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GridColorer, GridParams, PaletteColorer};
+    use crate::grid::{CellIndex, Grid};
+    use nannou::prelude::*;
+
+    #[test]
+    fn colorers_are_grid_colorers() {
+        let colorer: Box<dyn GridColorer> =
+            Box::new(PaletteColorer::seeded(vec![0.5], vec![1.0], vec![1.0], 0));
+        let num_cells = CellIndex { row: 2, col: 2 };
+        let grid = Grid::new(Rect::from_w_h(20.0, 20.0), &num_cells);
+        for cell in grid.row_major_iter() {
+            let params = GridParams {
+                cell: &cell,
+                total_num_cells: &num_cells,
+            };
+            assert_eq!(colorer.color(params), hsv(0.5, 1.0, 1.0));
+        }
+    }
 }
@@ -1,30 +1,32 @@
 use nannou::prelude::*;
-use crate::colorer::Colorer;
+use crate::colorer::{ContextColorer, UpdateTime};
 
 /// ModuloColorer invokes the underlying colorer every nth time.
-/// Every other time, this will return the `base_color`
-pub struct ModuloColorer {
+/// Every other time, this will return the `base_color`. It colors whatever
+/// the underlying colorer colors.
+pub struct ModuloColorer<K: ?Sized> {
     base_color: Hsv,
-    colorer: Box<dyn Colorer>,
+    colorer: Box<K>,
     iteration: i32,
     modulo_n: i32,
 }
 
-impl Colorer for ModuloColorer {
-    fn color(&self) -> Hsv {
+impl<C, K: ContextColorer<C> + ?Sized> ContextColorer<C> for ModuloColorer<K> {
+    fn color(&self, context: C) -> Hsv {
         if self.iteration % self.modulo_n == 0 {
-            return self.colorer.color();
+            return (*self.colorer).color(context);
         }
         self.base_color
     }
 
-    fn update(&mut self) {
+    fn update(&mut self, time: &UpdateTime) {
+        (*self.colorer).update(time);
         self.iteration += 1
     }
 }
 
-impl ModuloColorer {
-    pub fn new(colorer: Box<dyn Colorer>, base_color: Hsv, modulo_n: i32) -> Self {
+impl<K: ?Sized> ModuloColorer<K> {
+    pub fn new(colorer: Box<K>, base_color: Hsv, modulo_n: i32) -> Self {
         ModuloColorer {
             base_color,
             colorer,
@@ -33,3 +35,52 @@ impl ModuloColorer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ModuloColorer;
+    use crate::colorer::{
+        AlternatingColorer, ContextColorer, GridColorer, GridParams, NoiseColorer, UpdateTime,
+    };
+    use crate::grid::{CellIndex, Grid};
+    use nannou::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn colors_every_nth_update() {
+        let red = Hsv::new(0.0, 1.0, 1.0);
+        let white = Hsv::new(0.0, 0.0, 1.0);
+        let alternating: Box<dyn GridColorer> = Box::new(AlternatingColorer::new(vec![red]));
+        let mut colorer = ModuloColorer::new(alternating, white, 2);
+        let num_cells = CellIndex { row: 1, col: 1 };
+        let grid = Grid::new(Rect::from_w_h(10.0, 10.0), &num_cells);
+        let params = GridParams {
+            cell: &grid.get(&CellIndex { row: 0, col: 0 }).unwrap(),
+            total_num_cells: &num_cells,
+        };
+        assert_eq!(colorer.color(params), red);
+        colorer.update(&UpdateTime::default());
+        assert_eq!(colorer.color(params), white);
+        colorer.update(&UpdateTime::default());
+        assert_eq!(colorer.color(params), red);
+    }
+
+    #[test]
+    fn animates_the_underlying_colorer() {
+        let base = Hsv::new(200.0, 0.5, 0.5);
+        let noise: Box<dyn GridColorer> = Box::new(NoiseColorer::new(base, vec2(100.0, 100.0)));
+        let mut colorer = ModuloColorer::new(noise, Hsv::new(0.0, 0.0, 1.0), 1);
+        let num_cells = CellIndex { row: 1, col: 1 };
+        let grid = Grid::new(Rect::from_w_h(10.0, 10.0), &num_cells);
+        let params = GridParams {
+            cell: &grid.get(&CellIndex { row: 0, col: 0 }).unwrap(),
+            total_num_cells: &num_cells,
+        };
+        let before = colorer.color(params);
+        colorer.update(&UpdateTime::new(
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+        ));
+        assert_ne!(colorer.color(params), before);
+    }
+}
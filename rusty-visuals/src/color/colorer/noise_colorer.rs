@@ -0,0 +1,152 @@
+use crate::colorer::{ContextColorer, GridParams, UpdateTime};
+use nannou::noise::{NoiseFn, OpenSimplex, Seedable};
+use nannou::prelude::*;
+
+// Every channel samples the noise this far apart in time so that hue,
+// saturation and value don't all move together.
+const CHANNEL_NOISE_OFFSET: f64 = 40000.0;
+
+/// NoiseColorer perturbs a base color with simplex noise sampled at the
+/// center of every cell, so nearby cells get similar colors. `scale` is how
+/// far apart (in world coordinates, along x and y) two cells have to be for
+/// their colors to differ noticeably. Every channel moves by up to its
+/// amplitude away from the base color, and the noise moves forward in time
+/// by `speed` every second.
+pub struct NoiseColorer {
+    base_color: Hsv,
+    scale: Vec2,
+    // Hue amplitude is in degrees.
+    hue_amplitude: f32,
+    saturation_amplitude: f32,
+    value_amplitude: f32,
+    noise: OpenSimplex,
+    time: f64,
+    speed: f64,
+}
+
+impl<'a> ContextColorer<GridParams<'a>> for NoiseColorer {
+    fn color(&self, params: GridParams<'a>) -> Hsv {
+        let position = params.cell.xy / self.scale;
+        let sample = |channel: f64| {
+            self.noise.get([
+                position.x as f64,
+                position.y as f64,
+                self.time + channel * CHANNEL_NOISE_OFFSET,
+            ]) as f32
+        };
+        Hsv::new(
+            self.base_color.hue.to_positive_degrees() + sample(0.0) * self.hue_amplitude,
+            clamp(
+                self.base_color.saturation + sample(1.0) * self.saturation_amplitude,
+                0.0,
+                1.0,
+            ),
+            clamp(
+                self.base_color.value + sample(2.0) * self.value_amplitude,
+                0.0,
+                1.0,
+            ),
+        )
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        self.time += self.speed * time.since_last.as_secs_f64();
+    }
+}
+
+impl NoiseColorer {
+    pub fn new(base_color: Hsv, scale: Vec2) -> Self {
+        NoiseColorer {
+            base_color,
+            scale,
+            hue_amplitude: 10.0,
+            saturation_amplitude: 0.1,
+            value_amplitude: 0.1,
+            noise: OpenSimplex::new(),
+            time: 0.0,
+            speed: 0.6,
+        }
+    }
+
+    /// The same seed always gives the same colors.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.noise = self.noise.set_seed(seed as u32);
+        self
+    }
+
+    pub fn hue_amplitude(mut self, degrees: f32) -> Self {
+        self.hue_amplitude = degrees;
+        self
+    }
+
+    pub fn saturation_amplitude(mut self, amplitude: f32) -> Self {
+        self.saturation_amplitude = amplitude;
+        self
+    }
+
+    pub fn value_amplitude(mut self, amplitude: f32) -> Self {
+        self.value_amplitude = amplitude;
+        self
+    }
+
+    /// How far the noise moves in time every second. Lower speeds give
+    /// slower changes.
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoiseColorer;
+    use crate::colorer::{ContextColorer, GridParams};
+    use crate::grid::{CellIndex, Grid};
+    use nannou::prelude::*;
+
+    fn colors(colorer: &NoiseColorer, grid: &Grid, num_cells: &CellIndex) -> Vec<Hsv> {
+        grid.row_major_iter()
+            .map(|cell| {
+                colorer.color(GridParams {
+                    cell: &cell,
+                    total_num_cells: num_cells,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn colors_are_seeded() {
+        let num_cells = CellIndex { row: 4, col: 4 };
+        let grid = Grid::new(Rect::from_w_h(400.0, 400.0), &num_cells);
+        let base = Hsv::new(200.0, 0.5, 0.5);
+        let colorer = |seed| NoiseColorer::new(base, vec2(100.0, 100.0)).seed(seed);
+        let first = colors(&colorer(3), &grid, &num_cells);
+        assert_eq!(first, colors(&colorer(3), &grid, &num_cells));
+        assert_ne!(first, colors(&colorer(4), &grid, &num_cells));
+    }
+
+    #[test]
+    fn neighboring_cells_get_close_colors() {
+        let num_cells = CellIndex { row: 20, col: 20 };
+        let grid = Grid::new(Rect::from_w_h(200.0, 200.0), &num_cells);
+        let colorer = NoiseColorer::new(Hsv::new(200.0, 0.5, 0.5), vec2(100.0, 100.0))
+            .hue_amplitude(40.0)
+            .seed(5);
+        let hues: Vec<f32> = colors(&colorer, &grid, &num_cells)
+            .iter()
+            .map(|color| color.hue.to_positive_degrees())
+            .collect();
+        // Cells are a tenth of the scale apart, so their hues differ by a
+        // small part of the 80 degrees the hue can move over.
+        for row in 0..20 {
+            for col in 0..19 {
+                let (left, right) = (hues[row * 20 + col], hues[row * 20 + col + 1]);
+                assert!((left - right).abs() < 10.0, "{} and {}", left, right);
+            }
+        }
+        let spread = hues.iter().cloned().fold(f32::MIN, f32::max)
+            - hues.iter().cloned().fold(f32::MAX, f32::min);
+        assert!(spread > 10.0);
+    }
+}
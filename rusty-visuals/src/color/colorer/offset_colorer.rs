@@ -0,0 +1,73 @@
+use crate::colorer::{ContextColorer, UpdateTime};
+use nannou::prelude::*;
+
+/// OffsetColorer shifts the colors of another colorer: the hue by some degrees and the
+/// saturation and value by some amount, clamped between 0 and 1. It colors whatever the
+/// underlying colorer colors.
+pub struct OffsetColorer<K: ?Sized> {
+    colorer: Box<K>,
+    // Hue offset is in degrees.
+    hue_offset: f32,
+    saturation_offset: f32,
+    value_offset: f32,
+}
+
+impl<C, K: ContextColorer<C> + ?Sized> ContextColorer<C> for OffsetColorer<K> {
+    fn color(&self, context: C) -> Hsv {
+        let color = (*self.colorer).color(context);
+        Hsv::new(
+            (color.hue.to_positive_degrees() + self.hue_offset).rem_euclid(360.0),
+            clamp(color.saturation + self.saturation_offset, 0.0, 1.0),
+            clamp(color.value + self.value_offset, 0.0, 1.0),
+        )
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        (*self.colorer).update(time);
+    }
+}
+
+impl<K: ?Sized> OffsetColorer<K> {
+    pub fn new(colorer: Box<K>) -> Self {
+        OffsetColorer {
+            colorer,
+            hue_offset: 0.0,
+            saturation_offset: 0.0,
+            value_offset: 0.0,
+        }
+    }
+
+    pub fn hue_offset(mut self, degrees: f32) -> Self {
+        self.hue_offset = degrees;
+        self
+    }
+
+    pub fn saturation_offset(mut self, offset: f32) -> Self {
+        self.saturation_offset = offset;
+        self
+    }
+
+    pub fn value_offset(mut self, offset: f32) -> Self {
+        self.value_offset = offset;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OffsetColorer;
+    use crate::colorer::{ContextColorer, SolidColorer};
+    use nannou::prelude::*;
+
+    #[test]
+    fn offsets_wrap_the_hue_and_clamp_the_rest() {
+        let colorer = OffsetColorer::new(Box::new(SolidColorer(Hsv::new(300.0, 0.5, 0.9))))
+            .hue_offset(90.0)
+            .saturation_offset(-0.2)
+            .value_offset(0.3);
+        let color = colorer.color(());
+        assert!((color.hue.to_positive_degrees() - 30.0).abs() < 1e-3);
+        assert!((color.saturation - 0.3).abs() < 1e-6);
+        assert_eq!(color.value, 1.0);
+    }
+}
@@ -1,50 +1,102 @@
-use crate::colorer::{Colorer, GridColorer, GridParams};
+use crate::colorer::{Colorer, UpdateTime};
+use crate::palettes::Palette;
 use nannou::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
 
-pub struct PaletteColorer {
-    hues: Vec<f32>,
-    saturations: Vec<f32>,
-    values: Vec<f32>,
+/// PaletteColorer picks a random hue, saturation and value out of its
+/// palette every time it colors, or a random color out of a Palette. The
+/// colors come from its own rng, so a seeded PaletteColorer always gives the
+/// same sequence of colors.
+pub struct PaletteColorer<R = StdRng> {
+    choices: Choices,
+    // `color` only borrows the colorer, but every color moves the rng forward.
+    rng: RefCell<R>,
 }
 
-impl GridColorer for PaletteColorer {
-    fn color(&self, _: GridParams) -> Hsv {
-        self.color()
-    }
-
-    fn update(&mut self) {}
+enum Choices {
+    // Every channel is picked on its own.
+    Channels {
+        hues: Vec<f32>,
+        saturations: Vec<f32>,
+        values: Vec<f32>,
+    },
+    Colors(Vec<Hsv>),
 }
 
-impl Colorer for PaletteColorer {
+impl<R: Rng> Colorer for PaletteColorer<R> {
     fn color(&self) -> Hsv {
-        self.color()
+        self.random_color()
     }
-    fn update(&mut self) {}
+    fn update(&mut self, _: &UpdateTime) {}
 }
 
 impl PaletteColorer {
+    /// A PaletteColorer with different colors on every run. Use `seeded` to
+    /// reproduce an image.
     pub fn new(hues: Vec<f32>, saturations: Vec<f32>, values: Vec<f32>) -> Self {
-        if hues.len() == 0 || saturations.len() == 0 || values.len() == 0 {
+        PaletteColorer::with_rng(hues, saturations, values, StdRng::from_entropy())
+    }
+
+    pub fn seeded(hues: Vec<f32>, saturations: Vec<f32>, values: Vec<f32>, seed: u64) -> Self {
+        PaletteColorer::with_rng(hues, saturations, values, StdRng::seed_from_u64(seed))
+    }
+
+    /// Picks whole colors out of the palette (ex. one loaded from a file).
+    pub fn from_palette(palette: &Palette) -> Self {
+        PaletteColorer::from_palette_with_rng(palette, StdRng::from_entropy())
+    }
+
+    pub fn from_palette_seeded(palette: &Palette, seed: u64) -> Self {
+        PaletteColorer::from_palette_with_rng(palette, StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: Rng> PaletteColorer<R> {
+    pub fn with_rng(hues: Vec<f32>, saturations: Vec<f32>, values: Vec<f32>, rng: R) -> Self {
+        if hues.is_empty() || saturations.is_empty() || values.is_empty() {
             panic!("hues or saturations or values must not be empty");
         }
         PaletteColorer {
-            hues,
-            saturations,
-            values,
+            choices: Choices::Channels {
+                hues,
+                saturations,
+                values,
+            },
+            rng: RefCell::new(rng),
         }
     }
 
-    fn color(&self) -> Hsv {
-        let mut rng = thread_rng();
-        let hue_idx = rng.gen_range(0, self.hues.len());
-        let saturations_idx = rng.gen_range(0, self.saturations.len());
-        let values_idx = rng.gen_range(0, self.values.len());
-        hsv(
-            self.hues[hue_idx],
-            self.saturations[saturations_idx],
-            self.values[values_idx],
-        )
+    pub fn from_palette_with_rng(palette: &Palette, rng: R) -> Self {
+        if palette.colors.is_empty() {
+            panic!("the palette must not be empty");
+        }
+        PaletteColorer {
+            choices: Choices::Colors(palette.colors.clone()),
+            rng: RefCell::new(rng),
+        }
+    }
+
+    fn random_color(&self) -> Hsv {
+        let mut rng = self.rng.borrow_mut();
+        match &self.choices {
+            Choices::Channels {
+                hues,
+                saturations,
+                values,
+            } => {
+                let hue_idx = rng.gen_range(0, hues.len());
+                let saturations_idx = rng.gen_range(0, saturations.len());
+                let values_idx = rng.gen_range(0, values.len());
+                hsv(
+                    hues[hue_idx],
+                    saturations[saturations_idx],
+                    values[values_idx],
+                )
+            }
+            Choices::Colors(colors) => colors[rng.gen_range(0, colors.len())],
+        }
     }
 }
 
@@ -57,21 +109,36 @@ impl Colorer for PastelColorer {
         self.colorer.color()
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, _: &UpdateTime) {}
 }
 
 impl PastelColorer {
     pub fn new() -> Self {
-        let colorer = PaletteColorer::new(
-            (0..360).map(|n| n as f32 / 360.0).collect(),
+        PastelColorer::from_palette_colorer(PaletteColorer::new(
+            PastelColorer::hues(),
             vec![0.6],
             vec![1.0],
-        );
+        ))
+    }
 
+    pub fn seeded(seed: u64) -> Self {
+        PastelColorer::from_palette_colorer(PaletteColorer::seeded(
+            PastelColorer::hues(),
+            vec![0.6],
+            vec![1.0],
+            seed,
+        ))
+    }
+
+    fn from_palette_colorer(colorer: PaletteColorer) -> Self {
         PastelColorer {
             colorer: Box::new(colorer),
         }
     }
+
+    fn hues() -> Vec<f32> {
+        (0..360).map(|n| n as f32 / 360.0).collect()
+    }
 }
 
 impl Default for PastelColorer {
@@ -79,3 +146,45 @@ impl Default for PastelColorer {
         PastelColorer::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{PaletteColorer, PastelColorer};
+    use crate::colorer::Colorer;
+    use crate::palettes::Palette;
+    use nannou::prelude::*;
+
+    fn colors(colorer: &dyn Colorer) -> Vec<Hsv> {
+        (0..20).map(|_| colorer.color()).collect()
+    }
+
+    #[test]
+    fn seeded_colorers_repeat_their_colors() {
+        let palette = || {
+            (
+                vec![0.1, 0.2, 0.3, 0.4],
+                vec![0.5, 0.6, 0.7],
+                vec![0.8, 0.9],
+            )
+        };
+        let seeded = |seed: u64| {
+            let (hues, saturations, values) = palette();
+            PaletteColorer::seeded(hues, saturations, values, seed)
+        };
+        assert_eq!(colors(&seeded(5)), colors(&seeded(5)));
+        assert_ne!(colors(&seeded(5)), colors(&seeded(6)));
+
+        assert_eq!(
+            colors(&PastelColorer::seeded(5)),
+            colors(&PastelColorer::seeded(5))
+        );
+
+        let palette = Palette::new(vec![hsv(0.1, 0.2, 0.3), hsv(0.4, 0.5, 0.6)]);
+        let palette_colors = colors(&PaletteColorer::from_palette_seeded(&palette, 5));
+        assert_eq!(
+            palette_colors,
+            colors(&PaletteColorer::from_palette_seeded(&palette, 5))
+        );
+        assert!(palette_colors.iter().all(|c| palette.colors.contains(c)));
+    }
+}
@@ -1,27 +1,129 @@
-use std::collections::VecDeque;
+use crate::colorer::{ContextColorer, UpdateTime};
 use nannou::prelude::*;
-use crate::colorer::{GridColorer, GridParams};
+use std::collections::VecDeque;
+use std::time::Duration;
 
 /// RotatingColorer keeps a VecDeque of colorers and will always use the front of the VecDeque
-/// as the current colorer. The colorer can be rotated by invoking the `update` method
-pub struct RotatingColorer {
-    colorers: VecDeque<Box<dyn GridColorer>>,
+/// as the current colorer. The colorer is rotated on every call to the `update` method, or once
+/// every `period` if one is set. Every update is passed on to all the colorers, so animated
+/// colorers keep moving while they wait for their turn.
+/// It colors whatever its colorers color (ex. `RotatingColorer<dyn GridColorer>` is a
+/// GridColorer and `RotatingColorer<dyn Colorer>` can color anything).
+pub struct RotatingColorer<K: ?Sized> {
+    colorers: VecDeque<Box<K>>,
+    period: Option<Duration>,
+    // Number of periods that had passed at the last update with a period set,
+    // or None before the first one.
+    periods: Option<u64>,
 }
 
-impl GridColorer for RotatingColorer {
-    fn color(&self, params: GridParams) -> Hsv {
+impl<C, K: ContextColorer<C> + ?Sized> ContextColorer<C> for RotatingColorer<K> {
+    fn color(&self, context: C) -> Hsv {
         let colorer = self.colorers.front().unwrap();
-        (*colorer).color(params)
+        (**colorer).color(context)
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        for colorer in self.colorers.iter_mut() {
+            (**colorer).update(time);
+        }
+        match self.period {
+            None => self.colorers.rotate_left(1),
+            Some(period) => {
+                // Catch up on every period that passed, even when frames are slow.
+                // The first update only starts counting, since the colorer may be created
+                // long after the app started.
+                let periods = (time.since_start.as_secs_f64() / period.as_secs_f64()) as u64;
+                let last = self.periods.unwrap_or(periods);
+                let rotations = periods.saturating_sub(last) % self.colorers.len() as u64;
+                self.colorers.rotate_left(rotations as usize);
+                self.periods = Some(periods);
+            }
+        }
+    }
+}
+
+impl<K: ?Sized> RotatingColorer<K> {
+    pub fn new(colorers: VecDeque<Box<K>>) -> Self {
+        if colorers.is_empty() {
+            panic!("colorers must not be empty");
+        }
+        RotatingColorer {
+            colorers,
+            period: None,
+            periods: None,
+        }
     }
 
-    fn update(&mut self) {
-        let front_colorer = self.colorers.pop_front().unwrap();
-        self.colorers.push_back(front_colorer);
+    /// Rotates once every period of time instead of on every update.
+    pub fn period(mut self, period: Duration) -> Self {
+        if period.is_zero() {
+            panic!("the period must be longer than 0");
+        }
+        self.period = Some(period);
+        self
     }
 }
 
-impl RotatingColorer {
-    pub fn new(colorers: VecDeque<Box<dyn GridColorer>>) -> Self {
-        RotatingColorer { colorers }
+#[cfg(test)]
+mod tests {
+    use super::RotatingColorer;
+    use crate::colorer::{Colorer, ContextColorer, SolidColorer, UpdateTime};
+    use nannou::prelude::*;
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    // Colorers of evenly spaced hues, starting from red.
+    fn solid_colorers(count: usize) -> VecDeque<Box<dyn Colorer>> {
+        (0..count)
+            .map(|n| {
+                let hue = n as f32 / count as f32;
+                Box::new(SolidColorer(hsv(hue, 1.0, 1.0))) as Box<dyn Colorer>
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rotates_once_per_period() {
+        let colorers = solid_colorers(3);
+        let mut colorer: Box<dyn ContextColorer<()>> =
+            Box::new(RotatingColorer::new(colorers).period(Duration::from_secs(1)));
+        let first = colorer.color(());
+        let at =
+            |millis: u64| UpdateTime::new(Duration::from_millis(millis), Duration::from_millis(16));
+
+        colorer.update(&at(900));
+        assert_eq!(colorer.color(()), first);
+        colorer.update(&at(1000));
+        assert_eq!(colorer.color(()), hsv(1.0 / 3.0, 1.0, 1.0));
+        // Two periods passed since the last update, so it rotates twice.
+        colorer.update(&at(3100));
+        assert_eq!(colorer.color(()), first);
+    }
+
+    #[test]
+    fn starts_counting_periods_at_the_first_update() {
+        let colorers = solid_colorers(2);
+        let mut colorer: Box<dyn ContextColorer<()>> =
+            Box::new(RotatingColorer::new(colorers).period(Duration::from_secs(1)));
+        let at = |secs: u64| UpdateTime::new(Duration::from_secs(secs), Duration::from_millis(16));
+
+        // Created long after the app started.
+        colorer.update(&at(7));
+        assert_eq!(colorer.color(()), hsv(0.0, 1.0, 1.0));
+        colorer.update(&at(8));
+        assert_eq!(colorer.color(()), hsv(0.5, 1.0, 1.0));
+    }
+
+    #[test]
+    fn rotates_on_every_update() {
+        let colorers = solid_colorers(2);
+        let mut colorer = RotatingColorer::new(colorers);
+        assert_eq!(colorer.color(()), hsv(0.0, 1.0, 1.0));
+        // update doesn't depend on what is colored, so it has to be named.
+        ContextColorer::<()>::update(&mut colorer, &UpdateTime::default());
+        assert_eq!(colorer.color(()), hsv(0.5, 1.0, 1.0));
+        ContextColorer::<()>::update(&mut colorer, &UpdateTime::default());
+        assert_eq!(colorer.color(()), hsv(0.0, 1.0, 1.0));
     }
 }
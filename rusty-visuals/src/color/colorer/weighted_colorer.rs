@@ -0,0 +1,82 @@
+use crate::colorer::{ContextColorer, UpdateTime};
+use nannou::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cell::RefCell;
+
+/// WeightedColorer picks one of its colorers at random every time it colors, each as often as
+/// its weight says relative to the others. The picks come from its own rng, so a seeded
+/// WeightedColorer always picks the same sequence of colorers.
+pub struct WeightedColorer<K: ?Sized> {
+    colorers: Vec<Box<K>>,
+    weights: WeightedIndex<f32>,
+    // `color` only borrows the colorer, but every pick moves the rng forward.
+    rng: RefCell<StdRng>,
+}
+
+impl<C, K: ContextColorer<C> + ?Sized> ContextColorer<C> for WeightedColorer<K> {
+    fn color(&self, context: C) -> Hsv {
+        let idx = self.weights.sample(&mut *self.rng.borrow_mut());
+        (*self.colorers[idx]).color(context)
+    }
+
+    fn update(&mut self, time: &UpdateTime) {
+        for colorer in self.colorers.iter_mut() {
+            (**colorer).update(time);
+        }
+    }
+}
+
+impl<K: ?Sized> WeightedColorer<K> {
+    /// A WeightedColorer with different picks on every run. Use `seeded` to reproduce an image.
+    pub fn new(choices: Vec<(f32, Box<K>)>) -> Self {
+        WeightedColorer::with_rng(choices, StdRng::from_entropy())
+    }
+
+    pub fn seeded(choices: Vec<(f32, Box<K>)>, seed: u64) -> Self {
+        WeightedColorer::with_rng(choices, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(choices: Vec<(f32, Box<K>)>, rng: StdRng) -> Self {
+        let (weights, colorers): (Vec<f32>, Vec<Box<K>>) = choices.into_iter().unzip();
+        let weights = WeightedIndex::new(&weights)
+            .expect("weights must not be empty or negative and must not all be 0");
+        WeightedColorer {
+            colorers,
+            weights,
+            rng: RefCell::new(rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WeightedColorer;
+    use crate::colorer::{Colorer, ContextColorer, SolidColorer};
+    use nannou::prelude::*;
+
+    fn colors(colorer: &dyn ContextColorer<()>) -> Vec<Hsv> {
+        (0..50).map(|_| colorer.color(())).collect()
+    }
+
+    #[test]
+    fn picks_by_weight() {
+        let single = |hue: f32| -> Box<dyn Colorer> { Box::new(SolidColorer(hsv(hue, 1.0, 1.0))) };
+        let weighted = |seed: u64, weights: (f32, f32)| {
+            WeightedColorer::seeded(
+                vec![(weights.0, single(0.0)), (weights.1, single(0.5))],
+                seed,
+            )
+        };
+        assert_eq!(
+            colors(&weighted(2, (1.0, 1.0))),
+            colors(&weighted(2, (1.0, 1.0)))
+        );
+        let red = hsv(0.0, 1.0, 1.0);
+        let cyan = hsv(0.5, 1.0, 1.0);
+        assert!(colors(&weighted(2, (1.0, 0.0))).iter().all(|c| *c == red));
+        assert!(colors(&weighted(2, (0.0, 1.0))).iter().all(|c| *c == cyan));
+        assert!(colors(&weighted(2, (3.0, 1.0))).contains(&cyan));
+    }
+}
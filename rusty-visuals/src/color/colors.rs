@@ -1,28 +1,32 @@
 use nannou::color::{self, Hsl};
 use nannou::prelude::*;
+use rand::Rng;
 
 const DEFAULT_SATURATION: color::DefaultScalar = 0.5;
 const DEFAULT_LIGHTNESS: color::DefaultScalar = 0.5;
 
 // Will pick a hue using a uniform probability distribution between hue_min and hue_max.
 // Saturation and Lightness in the HSL color palette is fixed to constants.
-pub fn random_color_in_hue_range(
+// Pass a seeded rng (ex. StdRng::seed_from_u64) to get the same colors on every run.
+pub fn random_color_in_hue_range<R: Rng>(
     hue_min: color::DefaultScalar,
     hue_max: color::DefaultScalar,
+    rng: &mut R,
 ) -> Hsl {
-    let hue = map_range(random_range(0.0, 1.0), 0.0, 1.0, hue_min, hue_max);
+    let hue = map_range(rng.gen_range(0.0, 1.0), 0.0, 1.0, hue_min, hue_max);
     Hsl::new(hue, DEFAULT_SATURATION, DEFAULT_LIGHTNESS)
 }
 
-pub fn random_color_in_range(
+pub fn random_color_in_range<R: Rng>(
     hue_min: color::DefaultScalar,
     hue_max: color::DefaultScalar,
     saturation_min: color::DefaultScalar,
     saturation_max: color::DefaultScalar,
+    rng: &mut R,
 ) -> Hsl {
-    let hue = map_range(random_range(0.0, 1.0), 0.0, 1.0, hue_min, hue_max);
+    let hue = map_range(rng.gen_range(0.0, 1.0), 0.0, 1.0, hue_min, hue_max);
     let saturation = map_range(
-        random_range(0.0, 1.0),
+        rng.gen_range(0.0, 1.0),
         0.0,
         1.0,
         saturation_min,
@@ -30,3 +34,33 @@ pub fn random_color_in_range(
     );
     Hsl::new(hue, saturation, DEFAULT_LIGHTNESS)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{random_color_in_hue_range, random_color_in_range};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn seeded_colors_repeat() {
+        let hues = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| random_color_in_hue_range(100.0, 200.0, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(hues(3), hues(3));
+        assert_ne!(hues(3), hues(4));
+
+        let colors = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| random_color_in_range(100.0, 200.0, 0.2, 0.8, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(colors(3), colors(3));
+        assert!(colors(3)
+            .iter()
+            .all(|c| c.saturation >= 0.2 && c.saturation <= 0.8));
+    }
+}
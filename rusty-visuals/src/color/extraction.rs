@@ -0,0 +1,296 @@
+/// Finds the dominant colors of an image. Both methods group the pixels in
+/// CIE L*a*b*, where distances follow how different colors look, and return
+/// the colors sorted by weight: the fraction of the image each one covers.
+///
+/// API Examples:
+///
+/// let image = ImageReader::open(path)?.decode()?.into_rgba8();
+/// let colors = extraction::kmeans(&image, 5, seed);
+/// let colors = extraction::median_cut(&image, 8);
+/// let colorer = PaletteColorer::from_palette(&extraction::palette(&colors));
+///
+use crate::color::spaces;
+use crate::palettes::Palette;
+use image::RgbaImage;
+use nannou::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Large photos are sampled down to about this many pixels before grouping.
+const MAX_SAMPLES: usize = 20000;
+const MAX_KMEANS_ITERATIONS: usize = 50;
+// Pixels more transparent than this are left out.
+const MIN_ALPHA: u8 = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedColor {
+    pub color: Hsv,
+    // The fraction of the sampled pixels closest to this color.
+    pub weight: f32,
+}
+
+/// Groups the pixels into up to `num_colors` clusters with k-means,
+/// starting from k-means++ centers. The same seed always gives the same
+/// colors. Images with fewer distinct colors give fewer clusters, and no
+/// colors are returned for 0 clusters or images without opaque pixels.
+pub fn kmeans(image: &RgbaImage, num_colors: usize, seed: u64) -> Vec<WeightedColor> {
+    let samples = sample_pixels(image);
+    if samples.is_empty() || num_colors == 0 {
+        return vec![];
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut centers = kmeans_plus_plus(&samples, num_colors, &mut rng);
+    let mut assignments = vec![0; samples.len()];
+    for iteration in 0..MAX_KMEANS_ITERATIONS {
+        let mut changed = false;
+        for (sample, assignment) in samples.iter().zip(assignments.iter_mut()) {
+            let closest = closest_center(sample, &centers);
+            if closest != *assignment {
+                *assignment = closest;
+                changed = true;
+            }
+        }
+        if !changed && iteration > 0 {
+            break;
+        }
+        let mut sums = vec![([0.0; 3], 0); centers.len()];
+        for (sample, &assignment) in samples.iter().zip(assignments.iter()) {
+            let (sum, count) = &mut sums[assignment];
+            add(sum, sample);
+            *count += 1;
+        }
+        for (center, (sum, count)) in centers.iter_mut().zip(sums) {
+            // An empty cluster keeps its center and may pick up pixels later.
+            if count > 0 {
+                *center = mean(sum, count);
+            }
+        }
+    }
+
+    let mut counts = vec![0; centers.len()];
+    for &assignment in assignments.iter() {
+        counts[assignment] += 1;
+    }
+    weighted_colors(centers.into_iter().zip(counts).collect(), samples.len())
+}
+
+/// Splits the pixels into `num_colors` boxes by repeatedly cutting the box
+/// with the widest spread at its median, along that spread. Every box
+/// becomes the average of its pixels. Median cut needs no seed: the same
+/// image always gives the same colors. No colors are returned for 0 boxes or
+/// images without opaque pixels.
+pub fn median_cut(image: &RgbaImage, num_colors: usize) -> Vec<WeightedColor> {
+    let samples = sample_pixels(image);
+    if samples.is_empty() || num_colors == 0 {
+        return vec![];
+    }
+    let total = samples.len();
+    let mut boxes = vec![samples];
+    // Boxes that can't be cut any further.
+    let mut uncut = vec![];
+    while boxes.len() + uncut.len() < num_colors {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .map(|(idx, pixels)| (idx, widest_channel(pixels)))
+            .max_by(|(_, (_, a)), (_, (_, b))| a.partial_cmp(b).unwrap());
+        let (idx, channel) = match widest {
+            Some((idx, (channel, _))) => (idx, channel),
+            None => break,
+        };
+        let mut pixels = boxes.swap_remove(idx);
+        match median_cut_index(&mut pixels, channel) {
+            Some(cut) => {
+                let upper = pixels.split_off(cut);
+                boxes.push(pixels);
+                boxes.push(upper);
+            }
+            None => uncut.push(pixels),
+        }
+    }
+    boxes.extend(uncut);
+
+    let centers = boxes
+        .iter()
+        .map(|pixels| {
+            let mut sum = [0.0; 3];
+            for pixel in pixels.iter() {
+                add(&mut sum, pixel);
+            }
+            (mean(sum, pixels.len()), pixels.len())
+        })
+        .collect();
+    weighted_colors(centers, total)
+}
+
+/// A Palette of the extracted colors, most common first.
+pub fn palette(colors: &[WeightedColor]) -> Palette {
+    Palette::new(colors.iter().map(|c| c.color).collect())
+}
+
+// Every pixel of small images, or evenly spaced pixels of large ones, in
+// L*a*b*.
+fn sample_pixels(image: &RgbaImage) -> Vec<[f32; 3]> {
+    let num_pixels = (image.width() * image.height()) as usize;
+    let step = num_pixels.div_ceil(MAX_SAMPLES);
+    image
+        .pixels()
+        .step_by(step.max(1))
+        .filter(|pixel| pixel.0[3] >= MIN_ALPHA)
+        .map(|pixel| {
+            let [r, g, b, _] = pixel.0;
+            spaces::srgb_to_lab([r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0])
+        })
+        .collect()
+}
+
+// The first center is a random pixel. Every next one is picked with a
+// probability proportional to its squared distance from the closest center,
+// which spreads the centers out across the image's colors.
+fn kmeans_plus_plus(samples: &[[f32; 3]], num_colors: usize, rng: &mut StdRng) -> Vec<[f32; 3]> {
+    let mut centers = vec![samples[rng.gen_range(0, samples.len())]];
+    let mut distances: Vec<f32> = samples
+        .iter()
+        .map(|sample| spaces::distance_squared(sample, &centers[0]))
+        .collect();
+    while centers.len() < num_colors {
+        let total: f32 = distances.iter().sum();
+        if total <= 0.0 {
+            // Every pixel already sits on a center.
+            break;
+        }
+        let mut target = rng.gen_range(0.0, total);
+        let mut picked = distances.len() - 1;
+        for (idx, distance) in distances.iter().enumerate() {
+            if target < *distance {
+                picked = idx;
+                break;
+            }
+            target -= distance;
+        }
+        let center = samples[picked];
+        for (sample, distance) in samples.iter().zip(distances.iter_mut()) {
+            *distance = distance.min(spaces::distance_squared(sample, &center));
+        }
+        centers.push(center);
+    }
+    centers
+}
+
+fn closest_center(sample: &[f32; 3], centers: &[[f32; 3]]) -> usize {
+    centers
+        .iter()
+        .map(|center| spaces::distance_squared(sample, center))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .map(|(idx, _)| idx)
+        .unwrap()
+}
+
+// Sorts the pixels along the channel and returns where to cut them. The
+// pixels with the median's value all stay in the same box: the cut is at the
+// first pixel above them or, when they run to the end, before the first of
+// them. None when every pixel has the same value.
+fn median_cut_index(pixels: &mut [[f32; 3]], channel: usize) -> Option<usize> {
+    pixels.sort_by(|a, b| a[channel].partial_cmp(&b[channel]).unwrap());
+    let median = pixels[pixels.len() / 2][channel];
+    let above = pixels.iter().position(|p| p[channel] > median);
+    let first = pixels.iter().position(|p| p[channel] == median);
+    match (above, first) {
+        (Some(cut), _) => Some(cut),
+        (None, Some(cut)) if cut > 0 => Some(cut),
+        _ => None,
+    }
+}
+
+// The channel with the largest range of values, and that range.
+fn widest_channel(pixels: &[[f32; 3]]) -> (usize, f32) {
+    (0..3)
+        .map(|channel| {
+            let (min, max) = pixels.iter().fold((f32::MAX, f32::MIN), |(min, max), p| {
+                (min.min(p[channel]), max.max(p[channel]))
+            });
+            (channel, max - min)
+        })
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .unwrap()
+}
+
+fn add(sum: &mut [f32; 3], color: &[f32; 3]) {
+    for (total, channel) in sum.iter_mut().zip(color.iter()) {
+        *total += channel;
+    }
+}
+
+fn mean(sum: [f32; 3], count: usize) -> [f32; 3] {
+    [
+        sum[0] / count as f32,
+        sum[1] / count as f32,
+        sum[2] / count as f32,
+    ]
+}
+
+fn weighted_colors(centers: Vec<([f32; 3], usize)>, total: usize) -> Vec<WeightedColor> {
+    let mut colors: Vec<WeightedColor> = centers
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(lab, count)| {
+            let [r, g, b] = spaces::lab_to_srgb(lab);
+            WeightedColor {
+                color: Srgb::new(r, g, b).into(),
+                weight: count as f32 / total as f32,
+            }
+        })
+        .collect();
+    // The sort is stable, so ties keep the order they were found in.
+    colors.sort_by(|a, b| b.weight.partial_cmp(&a.weight).unwrap());
+    colors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kmeans, median_cut, WeightedColor};
+    use image::{Rgba, RgbaImage};
+    use nannou::prelude::*;
+
+    // Three quarters red, one quarter blue.
+    fn two_color_image() -> RgbaImage {
+        RgbaImage::from_fn(40, 40, |x, _| {
+            if x < 30 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        })
+    }
+
+    fn assert_red_and_blue(colors: &[WeightedColor]) {
+        assert_eq!(colors.len(), 2);
+        let red: Srgb = colors[0].color.into();
+        let blue: Srgb = colors[1].color.into();
+        assert!(red.red > 0.99 && red.green < 0.01 && red.blue < 0.01);
+        assert!(blue.blue > 0.99 && blue.red < 0.01 && blue.green < 0.01);
+        assert!((colors[0].weight - 0.75).abs() < 1e-6);
+        assert!((colors[1].weight - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn finds_the_dominant_colors() {
+        let image = two_color_image();
+        // Asking for more colors than the image has gives back only its colors.
+        assert_red_and_blue(&kmeans(&image, 5, 1));
+        assert_red_and_blue(&median_cut(&image, 5));
+        assert!(kmeans(&image, 0, 1).is_empty());
+        assert!(median_cut(&image, 0).is_empty());
+    }
+
+    #[test]
+    fn kmeans_is_seeded() {
+        let image = RgbaImage::from_fn(64, 64, |x, y| {
+            Rgba([(x * 4) as u8, (y * 4) as u8, ((x + y) * 2) as u8, 255])
+        });
+        assert_eq!(kmeans(&image, 6, 9), kmeans(&image, 6, 9));
+        let weights: f32 = kmeans(&image, 6, 9).iter().map(|c| c.weight).sum();
+        assert!((weights - 1.0).abs() < 1e-4);
+    }
+}
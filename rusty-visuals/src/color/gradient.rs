@@ -0,0 +1,191 @@
+/// ColorGradient blends any number of colors placed at positions from 0 to
+/// 1, with its own easing curve between every pair of neighboring stops. It
+/// colors anything that can be placed along it: a point along a path, a
+/// particle's age, or a grid cell through a GradientColorer.
+///
+/// API Examples:
+///
+/// let gradient = ColorGradient::from_stops(vec![(0.0, navy), (0.7, coral), (1.0, sand)])
+///     .interpolation(Interpolation::Oklch)
+///     .segment_easing(0, Easing::EaseIn);
+/// draw.ellipse().xy(particle.position).color(gradient.get(particle.age / MAX_AGE));
+/// let colorer = GradientColorer::new(gradient, GradientMapping::Radial { center: vec2(0.5, 0.5) });
+///
+use crate::colorer::{ContextColorer, UpdateTime};
+use crate::spaces::Interpolation;
+use nannou::prelude::*;
+
+/// Easing reshapes how fast the colors change between two stops. Every
+/// curve goes from 0 at the first stop to 1 at the second.
+#[derive(Clone, Copy)]
+pub enum Easing {
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts fast and slows down.
+    EaseOut,
+    /// Slow at both stops (smoothstep).
+    EaseInOut,
+    /// Holds the first stop's color up to the second stop, for hard edges.
+    Step,
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Easing::Step => 0.0,
+            Easing::Custom(ease) => ease(t),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ColorGradient {
+    stops: Vec<ColorStop>,
+    interpolation: Interpolation,
+}
+
+#[derive(Clone, Copy)]
+struct ColorStop {
+    position: f32,
+    color: Hsv,
+    // The color in the gradient's interpolation space.
+    coords: [f32; 3],
+    // The easing from this stop to the next one.
+    easing: Easing,
+}
+
+impl ContextColorer<f32> for ColorGradient {
+    fn color(&self, position: f32) -> Hsv {
+        self.get(position)
+    }
+
+    fn update(&mut self, _: &UpdateTime) {}
+}
+
+impl ColorGradient {
+    /// Spreads the colors evenly from 0 to 1.
+    pub fn new(colors: Vec<Hsv>) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        ColorGradient::from_stops(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(idx, color)| (idx as f32 / last, color))
+                .collect(),
+        )
+    }
+
+    /// Every stop is a (position, color) pair. Stops don't need to be in
+    /// order, and two stops at the same position make a hard edge.
+    pub fn from_stops(stops: Vec<(f32, Hsv)>) -> Self {
+        if stops.is_empty() {
+            panic!("a gradient needs at least one color");
+        }
+        let interpolation = Interpolation::Hsv;
+        let mut stops: Vec<ColorStop> = stops
+            .into_iter()
+            .map(|(position, color)| ColorStop {
+                position,
+                color,
+                coords: interpolation.coords_of(color),
+                easing: Easing::Linear,
+            })
+            .collect();
+        // The sort is stable, so stops at the same position keep their order.
+        stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+        ColorGradient {
+            stops,
+            interpolation,
+        }
+    }
+
+    /// The color space the stops are blended in. Defaults to HSV.
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        for stop in self.stops.iter_mut() {
+            stop.coords = interpolation.coords_of(stop.color);
+        }
+        self
+    }
+
+    /// Uses the same easing between all the stops.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        for stop in self.stops.iter_mut() {
+            stop.easing = easing;
+        }
+        self
+    }
+
+    /// The easing between stop `segment` and the one after it, counting
+    /// stops in order of position.
+    pub fn segment_easing(mut self, segment: usize, easing: Easing) -> Self {
+        if segment + 1 >= self.stops.len() {
+            panic!(
+                "segment {} is out of range for a gradient with {} stops",
+                segment,
+                self.stops.len()
+            );
+        }
+        self.stops[segment].easing = easing;
+        self
+    }
+
+    /// The color at `position`. Positions before the first stop or after
+    /// the last one get the color of that stop.
+    pub fn get(&self, position: f32) -> Hsv {
+        let first = &self.stops[0];
+        let last = &self.stops[self.stops.len() - 1];
+        if position <= first.position {
+            return first.color;
+        }
+        if position >= last.position {
+            return last.color;
+        }
+        // The first stop is before `position` and the last one after it, so
+        // some segment holds it and has a non zero length.
+        let (from, to) = self
+            .stops
+            .iter()
+            .zip(self.stops.iter().skip(1))
+            .find(|(from, to)| from.position <= position && position < to.position)
+            .unwrap();
+        let t = (position - from.position) / (to.position - from.position);
+        self.interpolation.color_at(self.interpolation.lerp(
+            from.coords,
+            to.coords,
+            from.easing.apply(t),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorGradient, Easing};
+    use nannou::prelude::*;
+
+    #[test]
+    fn blends_between_stops() {
+        let gradient = ColorGradient::from_stops(vec![
+            (1.0, Hsv::new(0.0, 1.0, 1.0)),
+            (0.0, Hsv::new(0.0, 1.0, 0.0)),
+            (0.5, Hsv::new(0.0, 1.0, 0.5)),
+        ]);
+        assert_eq!(gradient.get(-1.0).value, 0.0);
+        assert_eq!(gradient.get(2.0).value, 1.0);
+        assert!((gradient.get(0.25).value - 0.25).abs() < 1e-5);
+        assert!((gradient.get(0.75).value - 0.75).abs() < 1e-5);
+
+        let eased = gradient
+            .clone()
+            .segment_easing(0, Easing::Step)
+            .segment_easing(1, Easing::EaseIn);
+        assert_eq!(eased.get(0.4).value, 0.0);
+        assert!((eased.get(0.75).value - 0.625).abs() < 1e-5);
+    }
+}
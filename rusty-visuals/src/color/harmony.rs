@@ -0,0 +1,204 @@
+/// Palettes generated from a base color with the classic color harmony
+/// rules. The hues come from the rule, and a seeded amount of variation in
+/// saturation and value keeps the palettes from looking mechanical.
+///
+/// API Examples:
+///
+/// let palette = HarmonyBuilder::new(Harmony::SplitComplementary, hsv(0.6, 0.5, 0.9))
+///     .shades(3)
+///     .value_variation(0.1)
+///     .seed(model.seed)
+///     .build();
+/// let colorer = AlternatingColorer::from_palette(&palette);
+/// let colorer = PaletteColorer::from_palette_seeded(&palette, model.seed);
+///
+use crate::palettes::Palette;
+use nannou::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// The darkest shade of a hue has this fraction of the hue's value.
+const DARKEST_SHADE: f32 = 0.5;
+
+/// Harmony rules, named after where their hues sit on the color wheel
+/// relative to the base hue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Harmony {
+    /// The base hue and the one opposite it.
+    Complementary,
+    /// The base hue and the two hues next to its complement.
+    SplitComplementary,
+    /// The base hue and its neighbors, 30 degrees away on both sides.
+    Analogous,
+    /// Three hues evenly spaced around the wheel.
+    Triadic,
+    /// Four hues evenly spaced around the wheel.
+    Tetradic,
+    /// Only the base hue, as the base color, a tint (less saturated), a
+    /// shade (darker) and a tone (both).
+    Monochromatic,
+}
+
+impl Harmony {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Harmony::Complementary => "complementary",
+            Harmony::SplitComplementary => "split complementary",
+            Harmony::Analogous => "analogous",
+            Harmony::Triadic => "triadic",
+            Harmony::Tetradic => "tetradic",
+            Harmony::Monochromatic => "monochromatic",
+        }
+    }
+
+    // Every color of the rule as (hue offset in degrees, saturation scale,
+    // value scale) from the base color. The base color always comes first.
+    fn offsets(&self) -> Vec<(f32, f32, f32)> {
+        fn hues(degrees: &[f32]) -> Vec<(f32, f32, f32)> {
+            degrees.iter().map(|&d| (d, 1.0, 1.0)).collect()
+        }
+        match self {
+            Harmony::Complementary => hues(&[0.0, 180.0]),
+            Harmony::SplitComplementary => hues(&[0.0, 150.0, 210.0]),
+            Harmony::Analogous => hues(&[0.0, 30.0, -30.0]),
+            Harmony::Triadic => hues(&[0.0, 120.0, 240.0]),
+            Harmony::Tetradic => hues(&[0.0, 90.0, 180.0, 270.0]),
+            Harmony::Monochromatic => vec![
+                (0.0, 1.0, 1.0),
+                (0.0, 0.5, 1.0),
+                (0.0, 1.0, 0.6),
+                (0.0, 0.5, 0.6),
+            ],
+        }
+    }
+}
+
+/// HarmonyBuilder holds the settings of a harmony palette. Without any
+/// variation the palette is exactly the rule's colors and the seed doesn't
+/// matter.
+pub struct HarmonyBuilder {
+    harmony: Harmony,
+    base: Hsv,
+    shades: usize,
+    saturation_variation: f32,
+    value_variation: f32,
+    seed: u64,
+}
+
+impl HarmonyBuilder {
+    pub fn new(harmony: Harmony, base: Hsv) -> Self {
+        HarmonyBuilder {
+            harmony,
+            base,
+            shades: 1,
+            saturation_variation: 0.0,
+            value_variation: 0.0,
+            seed: 0,
+        }
+    }
+
+    /// How many colors every color of the rule turns into. The extra colors
+    /// are evenly spaced shades, down to half the value of the first.
+    pub fn shades(mut self, shades: usize) -> Self {
+        if shades == 0 {
+            panic!("shades must be at least 1");
+        }
+        self.shades = shades;
+        self
+    }
+
+    /// Every color's saturation moves by up to this much at random.
+    pub fn saturation_variation(mut self, variation: f32) -> Self {
+        self.saturation_variation = variation;
+        self
+    }
+
+    /// Every color's value moves by up to this much at random.
+    pub fn value_variation(mut self, variation: f32) -> Self {
+        self.value_variation = variation;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The palette, ordered by the rule's colors and then from light to
+    /// dark shades. The same settings always give the same palette.
+    pub fn build(&self) -> Palette {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut colors = vec![];
+        for (hue_offset, saturation_scale, value_scale) in self.harmony.offsets() {
+            let hue = self.base.hue.to_positive_degrees() + hue_offset;
+            let saturation = self.base.saturation * saturation_scale;
+            let value = self.base.value * value_scale;
+            for shade in 0..self.shades {
+                let darkening = if self.shades > 1 {
+                    1.0 - (1.0 - DARKEST_SHADE) * shade as f32 / (self.shades - 1) as f32
+                } else {
+                    1.0
+                };
+                colors.push(Hsv::new(
+                    hue.rem_euclid(360.0),
+                    vary(saturation, self.saturation_variation, &mut rng),
+                    vary(value * darkening, self.value_variation, &mut rng),
+                ));
+            }
+        }
+        Palette {
+            name: Some(self.harmony.name().to_string()),
+            colors,
+        }
+    }
+}
+
+fn vary(channel: f32, variation: f32, rng: &mut StdRng) -> f32 {
+    if variation <= 0.0 {
+        return channel;
+    }
+    clamp(channel + rng.gen_range(-variation, variation), 0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Harmony, HarmonyBuilder};
+    use nannou::prelude::*;
+
+    #[test]
+    fn follows_the_rule() {
+        let base = Hsv::new(300.0, 0.5, 0.8);
+        let hues: Vec<f32> = HarmonyBuilder::new(Harmony::Triadic, base)
+            .build()
+            .colors
+            .iter()
+            .map(|c| c.hue.to_positive_degrees().round())
+            .collect();
+        assert_eq!(hues, vec![300.0, 60.0, 180.0]);
+
+        let palette = HarmonyBuilder::new(Harmony::Complementary, base)
+            .shades(3)
+            .build();
+        assert_eq!(palette.colors.len(), 6);
+        for (color, value) in palette.colors[..3].iter().zip(&[0.8, 0.6, 0.4]) {
+            assert!((color.value - value).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn variation_is_seeded() {
+        let build = |seed: u64| {
+            HarmonyBuilder::new(Harmony::Tetradic, Hsv::new(20.0, 0.9, 0.9))
+                .saturation_variation(0.2)
+                .value_variation(0.2)
+                .seed(seed)
+                .build()
+        };
+        assert_eq!(build(4), build(4));
+        assert_ne!(build(4), build(5));
+        assert!(build(4)
+            .colors
+            .iter()
+            .all(|c| c.saturation <= 1.0 && c.value <= 1.0));
+    }
+}
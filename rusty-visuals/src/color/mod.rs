@@ -1,2 +1,7 @@
 pub mod colors;
 pub mod colorer;
+pub mod extraction;
+pub mod gradient;
+pub mod harmony;
+pub mod palettes;
+pub mod spaces;
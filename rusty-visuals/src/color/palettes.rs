@@ -0,0 +1,484 @@
+/// Palettes loaded from the files color tools export: GIMP (.gpl), Adobe
+/// swatch exchange (.ase), plain lists of hex colors (.hex or .txt) and CSS
+/// custom properties (.css).
+///
+/// API Examples:
+///
+/// let palette = Palette::load("src/art/resources/palettes/colorpalettes-4291.hex").unwrap();
+/// let colorer = AlternatingColorer::new(palette.colors.clone());
+/// let colorer = PaletteColorer::from_palette(&palette);
+///
+use nannou::prelude::*;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    // The name stored in the file, if any.
+    pub name: Option<String>,
+    pub colors: Vec<Hsv>,
+}
+
+/// PaletteError is everything that can go wrong while loading a Palette.
+/// Syntax errors point at the line (or for .ase files, the byte) where the
+/// file stopped making sense.
+#[derive(Debug)]
+pub enum PaletteError {
+    Io(io::Error),
+    UnsupportedFormat(String),
+    Syntax { line: usize, message: String },
+    Binary { offset: usize, message: String },
+    Empty,
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaletteError::Io(err) => write!(f, "could not read the palette: {}", err),
+            PaletteError::UnsupportedFormat(extension) => write!(
+                f,
+                "unsupported palette format {:?}, expected gpl, ase, hex, txt or css",
+                extension
+            ),
+            PaletteError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            PaletteError::Binary { offset, message } => write!(f, "byte {}: {}", offset, message),
+            PaletteError::Empty => write!(f, "the palette has no colors"),
+        }
+    }
+}
+
+impl Error for PaletteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PaletteError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PaletteError {
+    fn from(err: io::Error) -> Self {
+        PaletteError::Io(err)
+    }
+}
+
+impl Palette {
+    pub fn new(colors: Vec<Hsv>) -> Self {
+        Palette { name: None, colors }
+    }
+
+    /// Loads a palette, picking the format from the file's extension.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Palette, PaletteError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match extension.as_str() {
+            "gpl" => Palette::from_gpl(&fs::read_to_string(path)?),
+            "ase" => Palette::from_ase(&fs::read(path)?),
+            "hex" | "txt" => Palette::from_hex_list(&fs::read_to_string(path)?),
+            "css" => Palette::from_css(&fs::read_to_string(path)?),
+            _ => Err(PaletteError::UnsupportedFormat(extension)),
+        }
+    }
+
+    /// Parses a GIMP palette: a `GIMP Palette` header, optional `Name:` and
+    /// `Columns:` lines, `#` comments and one `red green blue [name]` line
+    /// (0 to 255) per color.
+    pub fn from_gpl(text: &str) -> Result<Palette, PaletteError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+        match lines.next() {
+            Some((_, "GIMP Palette")) => (),
+            Some((line, _)) => return Err(syntax_error(line, "expected a `GIMP Palette` header")),
+            None => return Err(PaletteError::Empty),
+        }
+
+        let mut palette = Palette::new(vec![]);
+        for (line, content) in lines {
+            if content.starts_with('#') || content.starts_with("Columns:") {
+                continue;
+            }
+            if let Some(name) = content.strip_prefix("Name:") {
+                palette.name = Some(name.trim().to_string());
+                continue;
+            }
+            let channels = content
+                .split_whitespace()
+                .take(3)
+                .map(|channel| channel.parse::<u8>())
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| syntax_error(line, "expected red, green and blue from 0 to 255"))?;
+            if channels.len() != 3 {
+                return Err(syntax_error(
+                    line,
+                    "expected red, green and blue from 0 to 255",
+                ));
+            }
+            palette
+                .colors
+                .push(from_rgb8(channels[0], channels[1], channels[2]));
+        }
+        palette.non_empty()
+    }
+
+    /// Parses an Adobe swatch exchange file. RGB, CMYK and gray swatches are
+    /// read, groups are flattened in file order and the first group's name
+    /// becomes the palette's name.
+    pub fn from_ase(bytes: &[u8]) -> Result<Palette, PaletteError> {
+        let mut reader = AseReader { bytes, offset: 0 };
+        if reader.take(4)? != b"ASEF" {
+            return Err(reader.error(0, "expected the ASEF signature"));
+        }
+        // Version (major, minor), which every reader ignores.
+        reader.take(4)?;
+        let num_blocks = reader.u32()?;
+
+        let mut palette = Palette::new(vec![]);
+        for _ in 0..num_blocks {
+            let block_type = reader.u16()?;
+            let block_length = reader.u32()? as usize;
+            let block_start = reader.offset;
+            match block_type {
+                ASE_COLOR_ENTRY => {
+                    reader.name()?;
+                    let model = reader.take(4)?;
+                    let color = match model {
+                        b"RGB " => {
+                            let (r, g, b) = (reader.f32()?, reader.f32()?, reader.f32()?);
+                            Srgb::new(r, g, b).into()
+                        }
+                        b"CMYK" => {
+                            let (c, m, y) = (reader.f32()?, reader.f32()?, reader.f32()?);
+                            let k = reader.f32()?;
+                            Srgb::new(
+                                (1.0 - c) * (1.0 - k),
+                                (1.0 - m) * (1.0 - k),
+                                (1.0 - y) * (1.0 - k),
+                            )
+                            .into()
+                        }
+                        b"Gray" => {
+                            let gray = reader.f32()?;
+                            Srgb::new(gray, gray, gray).into()
+                        }
+                        _ => {
+                            return Err(reader.error(
+                                reader.offset - 4,
+                                &format!(
+                                    "unsupported color model {:?}",
+                                    String::from_utf8_lossy(model)
+                                ),
+                            ))
+                        }
+                    };
+                    palette.colors.push(color);
+                }
+                ASE_GROUP_START => {
+                    let name = reader.name()?;
+                    if palette.name.is_none() && !name.is_empty() {
+                        palette.name = Some(name);
+                    }
+                }
+                ASE_GROUP_END => (),
+                _ => return Err(reader.error(block_start - 6, "unknown block type")),
+            }
+            // Skip whatever is left of the block (ex. the color type).
+            if reader.offset > block_start + block_length {
+                return Err(reader.error(block_start, "block is longer than its length"));
+            }
+            reader.take(block_start + block_length - reader.offset)?;
+        }
+        palette.non_empty()
+    }
+
+    /// Parses one hex color (`#rrggbb`, `rrggbb`, `#rgb` or `#rrggbbaa`, whose
+    /// alpha is dropped) per line. Blank lines and `//` comments are skipped.
+    pub fn from_hex_list(text: &str) -> Result<Palette, PaletteError> {
+        let mut palette = Palette::new(vec![]);
+        for (i, line) in text.lines().enumerate() {
+            let content = line.split("//").next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            let color = parse_hex(content)
+                .ok_or_else(|| syntax_error(i + 1, &format!("{:?} is not a hex color", content)))?;
+            palette.colors.push(color);
+        }
+        palette.non_empty()
+    }
+
+    /// Reads every CSS custom property (`--name: value;`) whose value is a hex
+    /// color or an `rgb()`/`rgba()` color, in file order. Other custom
+    /// properties (ex. `--spacing: 4px`) are skipped.
+    pub fn from_css(text: &str) -> Result<Palette, PaletteError> {
+        let mut palette = Palette::new(vec![]);
+        for (i, line) in text.lines().enumerate() {
+            for declaration in line.split([';', '{', '}']) {
+                let declaration = declaration.trim();
+                if !declaration.starts_with("--") {
+                    continue;
+                }
+                let value = match declaration.split_once(':') {
+                    Some((_, value)) => value.trim(),
+                    None => return Err(syntax_error(i + 1, "expected `--name: value`")),
+                };
+                let color = if value.starts_with('#') {
+                    parse_hex(value)
+                } else if value.starts_with("rgb") {
+                    parse_css_rgb(value)
+                } else {
+                    continue;
+                };
+                let color = color.ok_or_else(|| {
+                    syntax_error(i + 1, &format!("{:?} is not a valid color", value))
+                })?;
+                palette.colors.push(color);
+            }
+        }
+        palette.non_empty()
+    }
+
+    fn non_empty(self) -> Result<Palette, PaletteError> {
+        if self.colors.is_empty() {
+            return Err(PaletteError::Empty);
+        }
+        Ok(self)
+    }
+}
+
+const ASE_COLOR_ENTRY: u16 = 0x0001;
+const ASE_GROUP_START: u16 = 0xc001;
+const ASE_GROUP_END: u16 = 0xc002;
+
+// Reads the big endian values of an .ase file, failing at the end of the file.
+struct AseReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> AseReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PaletteError> {
+        if self.offset + len > self.bytes.len() {
+            return Err(self.error(self.offset, "unexpected end of file"));
+        }
+        let taken = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(taken)
+    }
+
+    fn u16(&mut self) -> Result<u16, PaletteError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, PaletteError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32, PaletteError> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    // A name is its length in UTF-16 code units (with the trailing 0)
+    // followed by the code units.
+    fn name(&mut self) -> Result<String, PaletteError> {
+        let start = self.offset;
+        let len = self.u16()? as usize;
+        let units = (0..len)
+            .map(|_| self.u16())
+            .collect::<Result<Vec<u16>, _>>()?;
+        let units = units.split(|unit| *unit == 0).next().unwrap_or(&[]);
+        String::from_utf16(units).map_err(|_| self.error(start, "name is not valid UTF-16"))
+    }
+
+    fn error(&self, offset: usize, message: &str) -> PaletteError {
+        PaletteError::Binary {
+            offset,
+            message: message.to_string(),
+        }
+    }
+}
+
+fn syntax_error(line: usize, message: &str) -> PaletteError {
+    PaletteError::Syntax {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn from_rgb8(r: u8, g: u8, b: u8) -> Hsv {
+    Srgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0).into()
+}
+
+fn parse_hex(text: &str) -> Option<Hsv> {
+    let digits = text.strip_prefix('#').unwrap_or(text);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize, len: usize| u8::from_str_radix(&digits[i * len..(i + 1) * len], 16);
+    match digits.len() {
+        // The alpha of #rrggbbaa is dropped.
+        6 | 8 => Some(from_rgb8(
+            channel(0, 2).ok()?,
+            channel(1, 2).ok()?,
+            channel(2, 2).ok()?,
+        )),
+        // #rgb is short for #rrggbb.
+        3 => Some(from_rgb8(
+            channel(0, 1).ok()? * 17,
+            channel(1, 1).ok()? * 17,
+            channel(2, 1).ok()? * 17,
+        )),
+        _ => None,
+    }
+}
+
+// rgb(r, g, b) or rgba(r, g, b, a) with channels from 0 to 255. The alpha is
+// dropped.
+fn parse_css_rgb(text: &str) -> Option<Hsv> {
+    let inner = text
+        .strip_prefix("rgba(")
+        .or_else(|| text.strip_prefix("rgb("))?
+        .strip_suffix(')')?;
+    let channels = inner
+        .split(',')
+        .map(|channel| channel.trim().parse::<u8>().ok())
+        .collect::<Option<Vec<u8>>>();
+    match channels {
+        Some(channels) if channels.len() == 3 => {
+            Some(from_rgb8(channels[0], channels[1], channels[2]))
+        }
+        _ => {
+            // The alpha of rgba() is a float.
+            let parts: Vec<&str> = inner.split(',').map(|part| part.trim()).collect();
+            if parts.len() != 4 || parts[3].parse::<f32>().is_err() {
+                return None;
+            }
+            Some(from_rgb8(
+                parts[0].parse().ok()?,
+                parts[1].parse().ok()?,
+                parts[2].parse().ok()?,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Palette, PaletteError};
+    use nannou::prelude::*;
+
+    fn rgb(palette: &Palette) -> Vec<(u8, u8, u8)> {
+        palette
+            .colors
+            .iter()
+            .map(|color| {
+                let rgb: Srgb = (*color).into();
+                let channel = |c: f32| (c * 255.0).round() as u8;
+                (channel(rgb.red), channel(rgb.green), channel(rgb.blue))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn text_formats() {
+        let gpl = "GIMP Palette\nName: Beach\nColumns: 2\n# sand\n250 183  60\tSand\n  0 0 255\n";
+        let palette = Palette::from_gpl(gpl).unwrap();
+        assert_eq!(palette.name, Some("Beach".to_string()));
+        assert_eq!(rgb(&palette), vec![(250, 183, 60), (0, 0, 255)]);
+
+        let hex = "#fab73c\n\n0000ff // sky\n#fff\n#ff000080\n";
+        assert_eq!(
+            rgb(&Palette::from_hex_list(hex).unwrap()),
+            vec![(250, 183, 60), (0, 0, 255), (255, 255, 255), (255, 0, 0)]
+        );
+
+        let css = ":root {
+  --sand: #fab73cff;
+  --gap: 4px;
+  --sky: rgb(0, 0, 255); --foam: rgba(255, 255, 255, 0.5);
+}";
+        assert_eq!(
+            rgb(&Palette::from_css(css).unwrap()),
+            vec![(250, 183, 60), (0, 0, 255), (255, 255, 255)]
+        );
+    }
+
+    #[test]
+    fn ase_swatches() {
+        let mut ase = b"ASEF\x00\x01\x00\x00\x00\x00\x00\x04".to_vec();
+        let name = |name: &str| {
+            let mut bytes = ((name.len() + 1) as u16).to_be_bytes().to_vec();
+            for unit in name.encode_utf16().chain(std::iter::once(0)) {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            }
+            bytes
+        };
+        let mut block = |block_type: u16, data: Vec<u8>| {
+            ase.extend_from_slice(&block_type.to_be_bytes());
+            ase.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            ase.extend_from_slice(&data);
+        };
+        block(0xc001, name("Beach"));
+        let mut sand = name("Sand");
+        sand.extend_from_slice(b"RGB ");
+        for channel in [250.0f32 / 255.0, 183.0 / 255.0, 60.0 / 255.0].iter() {
+            sand.extend_from_slice(&channel.to_be_bytes());
+        }
+        sand.extend_from_slice(&2u16.to_be_bytes());
+        block(0x0001, sand);
+        let mut gray = name("Gray");
+        gray.extend_from_slice(b"Gray");
+        gray.extend_from_slice(&0.0f32.to_be_bytes());
+        gray.extend_from_slice(&2u16.to_be_bytes());
+        block(0x0001, gray);
+        block(0xc002, vec![]);
+
+        let palette = Palette::from_ase(&ase).unwrap();
+        assert_eq!(palette.name, Some("Beach".to_string()));
+        assert_eq!(rgb(&palette), vec![(250, 183, 60), (0, 0, 0)]);
+
+        match Palette::from_ase(&ase[..ase.len() - 10]) {
+            Err(PaletteError::Binary { .. }) => (),
+            other => panic!("expected a binary error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn malformed_files() {
+        let errors = vec![
+            Palette::from_gpl("Name: Beach\n250 183 60\n"),
+            Palette::from_gpl("GIMP Palette\n250 183\n"),
+            Palette::from_gpl("GIMP Palette\n250 183 600\n"),
+            Palette::from_hex_list("#fab73c\n#fab7\n"),
+            Palette::from_css(":root {\n  --sand: #fab73z;\n}"),
+        ];
+        let lines: Vec<usize> = errors
+            .into_iter()
+            .map(|result| match result {
+                Err(PaletteError::Syntax { line, .. }) => line,
+                other => panic!("expected a syntax error, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(lines, vec![1, 2, 2, 2, 2]);
+
+        assert!(matches!(
+            Palette::from_hex_list("\n// nothing\n"),
+            Err(PaletteError::Empty)
+        ));
+        assert!(matches!(
+            Palette::load("palette.aco"),
+            Err(PaletteError::UnsupportedFormat(_))
+        ));
+    }
+}
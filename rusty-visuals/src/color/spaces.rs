@@ -0,0 +1,273 @@
+/// The color spaces colors can be blended in, and the conversions between
+/// sRGB and the perceptual spaces the color tools work in.
+///
+/// API Examples:
+///
+/// let orange = Interpolation::Oklch.mix(red, yellow, 0.5);
+/// let colorer = InterpolatedColorer::new((red, yellow)).interpolation(Interpolation::Oklab);
+///
+use nannou::prelude::*;
+
+// The D65 white point, which sRGB uses.
+const WHITE: [f32; 3] = [0.95047, 1.0, 1.08883];
+const DELTA: f32 = 6.0 / 29.0;
+// Below this chroma (or saturation) a color is a gray and its hue is
+// meaningless, so blends keep the hue of the other color.
+const ACHROMATIC: f32 = 1e-3;
+
+pub(crate) fn srgb_to_linear(channel: f32) -> f32 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn linear_to_srgb(channel: f32) -> f32 {
+    if channel <= 0.003_130_8 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Interpolation is the color space two colors are blended in. Hsv and
+/// Oklch blend the hue along the shorter way around the color wheel, so red
+/// to magenta never passes through green.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    Hsv,
+    /// Blends the amount of light, which is how light mixes physically.
+    LinearSrgb,
+    /// Blends in OKLab, where equal steps look about equally different and
+    /// midpoints don't turn gray.
+    Oklab,
+    /// OKLab in polar form (lightness, chroma, hue), which keeps the
+    /// midpoints as saturated as the ends.
+    Oklch,
+}
+
+impl Interpolation {
+    /// The color `t` (from 0 to 1) of the way from `from` to `to`.
+    pub fn mix(&self, from: Hsv, to: Hsv, t: f32) -> Hsv {
+        self.color_at(self.lerp(self.coords_of(from), self.coords_of(to), t))
+    }
+
+    // The coordinates of the color in this space. Hues are in degrees.
+    pub(crate) fn coords_of(&self, color: Hsv) -> [f32; 3] {
+        let rgb: Srgb = color.into();
+        let rgb = [rgb.red, rgb.green, rgb.blue];
+        match self {
+            Interpolation::Hsv => [
+                color.hue.to_positive_degrees(),
+                color.saturation,
+                color.value,
+            ],
+            Interpolation::LinearSrgb => [
+                srgb_to_linear(rgb[0]),
+                srgb_to_linear(rgb[1]),
+                srgb_to_linear(rgb[2]),
+            ],
+            Interpolation::Oklab => srgb_to_oklab(rgb),
+            Interpolation::Oklch => {
+                let [lightness, a, b] = srgb_to_oklab(rgb);
+                let hue = b.atan2(a).to_degrees();
+                [lightness, a.hypot(b), hue.rem_euclid(360.0)]
+            }
+        }
+    }
+
+    // The color of coordinates in this space, clamped to the sRGB gamut.
+    pub(crate) fn color_at(&self, coords: [f32; 3]) -> Hsv {
+        let rgb = match self {
+            Interpolation::Hsv => {
+                return Hsv::new(
+                    coords[0].rem_euclid(360.0),
+                    coords[1].clamp(0.0, 1.0),
+                    coords[2].clamp(0.0, 1.0),
+                )
+            }
+            Interpolation::LinearSrgb => [
+                linear_to_srgb(coords[0].clamp(0.0, 1.0)),
+                linear_to_srgb(coords[1].clamp(0.0, 1.0)),
+                linear_to_srgb(coords[2].clamp(0.0, 1.0)),
+            ],
+            Interpolation::Oklab => oklab_to_srgb(coords),
+            Interpolation::Oklch => {
+                let [lightness, chroma, hue] = coords;
+                let (sin, cos) = hue.to_radians().sin_cos();
+                let chroma = chroma.max(0.0);
+                oklab_to_srgb([lightness, chroma * cos, chroma * sin])
+            }
+        };
+        Srgb::new(rgb[0], rgb[1], rgb[2]).into()
+    }
+
+    // Blends coordinates of this space.
+    pub(crate) fn lerp(&self, from: [f32; 3], to: [f32; 3], t: f32) -> [f32; 3] {
+        let from = self.with_hue_of(from, to);
+        let difference = self.difference(from, to);
+        [
+            from[0] + difference[0] * t,
+            from[1] + difference[1] * t,
+            from[2] + difference[2] * t,
+        ]
+    }
+
+    // How far `to` is from `from`, with hues going the shorter way around.
+    pub(crate) fn difference(&self, from: [f32; 3], to: [f32; 3]) -> [f32; 3] {
+        let (from, to) = (self.with_hue_of(from, to), self.with_hue_of(to, from));
+        let mut difference = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+        if let Some((hue, _)) = self.hue_channel() {
+            let arc = difference[hue].rem_euclid(360.0);
+            difference[hue] = if arc > 180.0 { arc - 360.0 } else { arc };
+        }
+        difference
+    }
+
+    // The index of the hue and of the channel that says how colorful the
+    // color is (saturation or chroma).
+    fn hue_channel(&self) -> Option<(usize, usize)> {
+        match self {
+            Interpolation::Hsv => Some((0, 1)),
+            Interpolation::Oklch => Some((2, 1)),
+            Interpolation::LinearSrgb | Interpolation::Oklab => None,
+        }
+    }
+
+    // A gray takes the hue of the other color, so blending towards it only
+    // fades the other color out.
+    fn with_hue_of(&self, color: [f32; 3], other: [f32; 3]) -> [f32; 3] {
+        let mut color = color;
+        if let Some((hue, colorfulness)) = self.hue_channel() {
+            if color[colorfulness] < ACHROMATIC {
+                color[hue] = other[hue];
+            }
+        }
+        color
+    }
+}
+
+/// CIE L*a*b* (D65): L goes from 0 to 100, and equal distances look about
+/// equally different.
+pub(crate) fn srgb_to_lab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = [
+        srgb_to_linear(rgb[0]),
+        srgb_to_linear(rgb[1]),
+        srgb_to_linear(rgb[2]),
+    ];
+    let xyz = [
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b,
+        0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b,
+    ];
+    let f = |t: f32| {
+        if t > DELTA.powi(3) {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+    let [fx, fy, fz] = [
+        f(xyz[0] / WHITE[0]),
+        f(xyz[1] / WHITE[1]),
+        f(xyz[2] / WHITE[2]),
+    ];
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// The sRGB color of a L*a*b* color, clamped to the sRGB gamut.
+pub(crate) fn lab_to_srgb(lab: [f32; 3]) -> [f32; 3] {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let (fx, fz) = (fy + lab[1] / 500.0, fy - lab[2] / 200.0);
+    let f_inverse = |t: f32| {
+        if t > DELTA {
+            t.powi(3)
+        } else {
+            3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+        }
+    };
+    let [x, y, z] = [
+        WHITE[0] * f_inverse(fx),
+        WHITE[1] * f_inverse(fy),
+        WHITE[2] * f_inverse(fz),
+    ];
+    let linear = [
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+    ];
+    [
+        linear_to_srgb(linear[0]).clamp(0.0, 1.0),
+        linear_to_srgb(linear[1]).clamp(0.0, 1.0),
+        linear_to_srgb(linear[2]).clamp(0.0, 1.0),
+    ]
+}
+
+pub(crate) fn distance_squared(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+/// OKLab: L goes from 0 to 1.
+pub(crate) fn srgb_to_oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = [
+        srgb_to_linear(rgb[0]),
+        srgb_to_linear(rgb[1]),
+        srgb_to_linear(rgb[2]),
+    ];
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// The sRGB color of an OKLab color, clamped to the sRGB gamut.
+pub(crate) fn oklab_to_srgb(lab: [f32; 3]) -> [f32; 3] {
+    let l = (lab[0] + 0.396_337_78 * lab[1] + 0.215_803_76 * lab[2]).powi(3);
+    let m = (lab[0] - 0.105_561_346 * lab[1] - 0.063_854_17 * lab[2]).powi(3);
+    let s = (lab[0] - 0.089_484_18 * lab[1] - 1.291_485_5 * lab[2]).powi(3);
+    let linear = [
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    ];
+    [
+        linear_to_srgb(linear[0].clamp(0.0, 1.0)),
+        linear_to_srgb(linear[1].clamp(0.0, 1.0)),
+        linear_to_srgb(linear[2].clamp(0.0, 1.0)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interpolation;
+    use nannou::prelude::*;
+
+    #[test]
+    fn mixes_start_and_end_at_the_colors() {
+        let from = Hsv::new(200.0, 0.7, 0.6);
+        let to = Hsv::new(30.0, 0.4, 0.9);
+        let spaces = [
+            Interpolation::Hsv,
+            Interpolation::LinearSrgb,
+            Interpolation::Oklab,
+            Interpolation::Oklch,
+        ];
+        for space in spaces.iter() {
+            for (t, expected) in [(0.0, from), (1.0, to)].iter() {
+                let mixed: Srgb = space.mix(from, to, *t).into();
+                let expected: Srgb = (*expected).into();
+                assert!((mixed.red - expected.red).abs() < 1e-3, "{:?}", space);
+                assert!((mixed.green - expected.green).abs() < 1e-3, "{:?}", space);
+                assert!((mixed.blue - expected.blue).abs() < 1e-3, "{:?}", space);
+            }
+        }
+        // Blending with gray keeps the hue instead of sweeping through others.
+        let faded = Interpolation::Oklch.mix(to, Hsv::new(0.0, 0.0, 0.5), 0.5);
+        assert!((faded.hue.to_positive_degrees() - 30.0).abs() < 10.0);
+    }
+}
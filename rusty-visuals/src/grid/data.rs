@@ -0,0 +1,212 @@
+/// Per cell data for a Grid: a Grid<T> stores a T for every cell next to the
+/// cell's geometry, so sketches don't need to keep a parallel array indexed
+/// by CellIndex.
+///
+/// API Examples:
+///
+/// let mut colors = Grid::new(bounding_rect, &CellIndex { row, col })
+///     .map(|cell, _| average_color(&image, cell));
+/// colors[CellIndex { row: 0, col: 0 }] = BLACK;
+/// for (cell, color) in colors.iter() {
+///     draw.rect().xy(cell.xy).wh(cell.wh).color(*color);
+/// }
+///
+use crate::grid::{Cell, CellIndex, Geometry, Grid, GridIterator};
+use std::ops::{Index, IndexMut};
+
+impl<T> Grid<T> {
+    /// Builds a grid with the same geometry (including merged cells and the
+    /// edge mode) holding the value returned by `f` for every cell.
+    /// `f` is called once for every (row, column) index in row major order.
+    /// Cells absorbed by a merge are given the merged cell, but their values
+    /// are never used: the merged cell's value is stored at its top left index.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Cell, &T) -> U,
+    {
+        let data = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(offset, value)| {
+                // Every offset is inside of the grid, so there always is a cell.
+                let cell = self.get(&self.index_of(offset)).unwrap();
+                f(cell, value)
+            })
+            .collect();
+        self.with_data(data)
+    }
+
+    /// The value stored for the cell at the given index. Like Grid::get,
+    /// cells absorbed by a merge resolve to the cell that owns the block.
+    pub fn data(&self, index: &CellIndex) -> Option<&T> {
+        let offset = self.offset_of(index)?;
+        self.data.get(offset)
+    }
+
+    pub fn data_mut(&mut self, index: &CellIndex) -> Option<&mut T> {
+        let offset = self.offset_of(index)?;
+        self.data.get_mut(offset)
+    }
+
+    /// Iterates over the cells and their values in row major order.
+    pub fn iter(&self) -> DataIterator<'_, T> {
+        DataIterator {
+            cells: self.row_major_iter(),
+            grid: self,
+        }
+    }
+
+    /// Iterates over the cells and mutable references to their values in
+    /// row major order.
+    pub fn iter_mut(&mut self) -> DataIteratorMut<'_, T> {
+        // Borrow the fields the cells are computed from apart from the values.
+        let geometry = Geometry {
+            top_left: self.inner_rect.top_left(),
+            num_cells: self.num_cells,
+            row_tracks: &self.row_tracks,
+            col_tracks: &self.col_tracks,
+            merged: &self.merged,
+            spans: &self.spans,
+        };
+        DataIteratorMut {
+            cols: self.num_cells.col,
+            cells: GridIterator::new(geometry),
+            next_offset: 0,
+            data: self.data.iter_mut(),
+        }
+    }
+
+    // A grid with the same geometry as this one holding different data.
+    fn with_data<U>(&self, data: Vec<U>) -> Grid<U> {
+        Grid {
+            rect: self.rect,
+            inner_rect: self.inner_rect,
+            row_tracks: self.row_tracks.clone(),
+            col_tracks: self.col_tracks.clone(),
+            num_cells: self.num_cells,
+            edge_mode: self.edge_mode,
+            merged: self.merged.clone(),
+            spans: self.spans.clone(),
+            data,
+        }
+    }
+
+    // The position in `data` of the value for the cell at the given index.
+    fn offset_of(&self, index: &CellIndex) -> Option<usize> {
+        let index = self.merged.get(index).unwrap_or(index);
+        if index.row >= self.num_cells.row || index.col >= self.num_cells.col {
+            return None;
+        }
+        Some(index.row * self.num_cells.col + index.col)
+    }
+
+    fn index_of(&self, offset: usize) -> CellIndex {
+        CellIndex {
+            row: offset / self.num_cells.col,
+            col: offset % self.num_cells.col,
+        }
+    }
+}
+
+impl<T> Index<CellIndex> for Grid<T> {
+    type Output = T;
+    fn index(&self, index: CellIndex) -> &T {
+        match self.data(&index) {
+            Some(value) => value,
+            None => panic!("{:?} is outside of the grid", index),
+        }
+    }
+}
+
+impl<T> IndexMut<CellIndex> for Grid<T> {
+    fn index_mut(&mut self, index: CellIndex) -> &mut T {
+        match self.data_mut(&index) {
+            Some(value) => value,
+            None => panic!("{:?} is outside of the grid", index),
+        }
+    }
+}
+
+pub struct DataIterator<'a, T> {
+    grid: &'a Grid<T>,
+    cells: GridIterator<'a>,
+}
+
+impl<'a, T> Iterator for DataIterator<'a, T> {
+    type Item = (Cell, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let cell = self.cells.next()?;
+        let grid = self.grid;
+        Some((cell, &grid[cell.index]))
+    }
+}
+
+pub struct DataIteratorMut<'a, T> {
+    cols: usize,
+    cells: GridIterator<'a>,
+    // The position in `data` of the next value `data` hands out.
+    next_offset: usize,
+    data: std::slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for DataIteratorMut<'a, T> {
+    type Item = (Cell, &'a mut T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let cell = self.cells.next()?;
+        // The cells and the values are both in row major order, but the
+        // values of the cells absorbed by a merge are skipped.
+        let offset = cell.index.row * self.cols + cell.index.col;
+        let value = self.data.nth(offset - self.next_offset)?;
+        self.next_offset = offset + 1;
+        Some((cell, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{CellIndex, Grid};
+    use nannou::prelude::*;
+
+    #[test]
+    fn values_are_stored_per_cell() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 4.0, 3.0);
+        let grid = Grid::new(rect, &CellIndex { row: 3, col: 4 });
+        let mut labels = grid.map(|cell, _| cell.index.row * 10 + cell.index.col);
+        assert_eq!(labels[CellIndex { row: 2, col: 1 }], 21);
+        assert!(labels.data(&CellIndex { row: 3, col: 0 }).is_none());
+
+        labels[CellIndex { row: 0, col: 3 }] = 100;
+        for (cell, label) in labels.iter_mut() {
+            if cell.index.row == 1 {
+                *label += 1;
+            }
+        }
+        let values: Vec<usize> = labels.iter().map(|(_, label)| *label).collect();
+        assert_eq!(values[..8], [0, 1, 2, 100, 11, 12, 13, 14]);
+
+        let doubled = labels.map(|_, label| label * 2);
+        assert_eq!(doubled[CellIndex { row: 1, col: 0 }], 22);
+        assert_eq!(doubled.row_major_iter().count(), 12);
+    }
+
+    #[test]
+    fn merged_cells_share_their_value() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 4.0, 4.0);
+        let mut grid = Grid::new(rect, &CellIndex { row: 4, col: 4 });
+        grid.merge(&CellIndex { row: 1, col: 1 }, &CellIndex { row: 2, col: 2 });
+        let mut counts = grid.map(|_, _| 0);
+
+        counts[CellIndex { row: 2, col: 2 }] += 1;
+        assert_eq!(counts[CellIndex { row: 1, col: 1 }], 1);
+        for (_, count) in counts.iter_mut() {
+            *count += 1;
+        }
+        assert_eq!(counts.iter().count(), 13);
+        let merged = counts
+            .iter()
+            .find(|(cell, _)| cell.span == CellIndex { row: 2, col: 2 })
+            .unwrap();
+        assert_eq!(*merged.1, 2);
+    }
+}
@@ -0,0 +1,238 @@
+/// Deformations of grid cells for cracked or hand made looks: a Cell becomes
+/// a polygon that can be jittered, inset, rotated, skewed and displaced by
+/// noise before it is drawn.
+///
+/// API Examples:
+///
+/// let mut rng = StdRng::seed_from_u64(model.seed);
+/// let noise = OpenSimplex::new().set_seed(model.seed as u32);
+/// for cell in grid.row_major_iter() {
+///     let polygon = cell
+///         .polygon()
+///         .subdivide(4)
+///         .displace(&noise, 0.01, 10.0)
+///         .jitter(0.05, &mut rng)
+///         .inset(4.0);
+///     draw.polygon().points(polygon.points().iter().cloned());
+/// }
+///
+use crate::grid::Cell;
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+use rand::Rng;
+
+// Noise is sampled this far apart for the x and y displacements so that the
+// two don't move together.
+const NOISE_OFFSET: f64 = 1000.0;
+
+impl Cell {
+    /// The corners of the cell as a polygon, in counter clockwise order from
+    /// the bottom left.
+    pub fn polygon(&self) -> CellPolygon {
+        let rect = Rect::from_xy_wh(self.xy, self.wh);
+        CellPolygon {
+            points: vec![
+                rect.bottom_left(),
+                rect.bottom_right(),
+                rect.top_right(),
+                rect.top_left(),
+            ],
+            xy: self.xy,
+            wh: self.wh,
+        }
+    }
+}
+
+/// CellPolygon is the outline of a Cell after any number of deformations.
+/// Rotations and skews happen around the center of the original cell, and
+/// jitter is relative to its size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellPolygon {
+    // In counter clockwise order.
+    points: Vec<Vec2>,
+    xy: Vec2,
+    wh: Vec2,
+}
+
+impl CellPolygon {
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    pub fn into_points(self) -> Vec<Vec2> {
+        self.points
+    }
+
+    /// Splits every edge into `segments` equal parts so that displace bends
+    /// the edges as well as moving the corners.
+    pub fn subdivide(mut self, segments: usize) -> Self {
+        let segments = segments.max(1);
+        let len = self.points.len();
+        self.points = (0..len)
+            .flat_map(|i| {
+                let (a, b) = (self.points[i], self.points[(i + 1) % len]);
+                (0..segments).map(move |s| a.lerp(b, s as f32 / segments as f32))
+            })
+            .collect();
+        self
+    }
+
+    /// Moves every point by a random offset of up to `amount` times the size
+    /// of the cell on each axis. The same seeded rng always gives the same
+    /// polygons.
+    pub fn jitter<R: Rng>(mut self, amount: f32, rng: &mut R) -> Self {
+        let max_offset = self.wh * amount;
+        for point in self.points.iter_mut() {
+            *point += vec2(
+                max_offset.x * rng.gen_range(-1.0, 1.0),
+                max_offset.y * rng.gen_range(-1.0, 1.0),
+            );
+        }
+        self
+    }
+
+    /// Moves every edge `amount` inwards, keeping the corners sharp. Negative
+    /// amounts move the edges outwards.
+    pub fn inset(mut self, amount: f32) -> Self {
+        let len = self.points.len();
+        // The inward normal of the edge from point i to point i + 1.
+        let normals: Vec<Vec2> = (0..len)
+            .map(|i| {
+                let edge = self.points[(i + 1) % len] - self.points[i];
+                vec2(-edge.y, edge.x).normalize_or_zero()
+            })
+            .collect();
+        for (i, point) in self.points.iter_mut().enumerate() {
+            let (before, after) = (normals[(i + len - 1) % len], normals[i]);
+            // Where the two moved edges meet. Very sharp corners would shoot
+            // off, so they are cut at four times the amount.
+            let miter = (before + after) / (1.0 + before.dot(after)).max(0.125);
+            *point += miter * amount;
+        }
+        self
+    }
+
+    pub fn outset(self, amount: f32) -> Self {
+        self.inset(-amount)
+    }
+
+    /// Turns the polygon counter clockwise around the center of the cell.
+    pub fn rotate(mut self, radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        let center = self.xy;
+        for point in self.points.iter_mut() {
+            let p = *point - center;
+            *point = center + vec2(p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+        }
+        self
+    }
+
+    /// Slants the polygon around the center of the cell: x moves by
+    /// tan(x_radians) for every unit up and y by tan(y_radians) for every
+    /// unit to the right.
+    pub fn skew(mut self, x_radians: f32, y_radians: f32) -> Self {
+        let (x_slope, y_slope) = (x_radians.tan(), y_radians.tan());
+        let center = self.xy;
+        for point in self.points.iter_mut() {
+            let p = *point - center;
+            *point = center + vec2(p.x + x_slope * p.y, p.y + y_slope * p.x);
+        }
+        self
+    }
+
+    /// Moves every point by up to `amount` in each axis following the noise at
+    /// the point's position times `frequency`. Neighboring cells move their
+    /// shared corners the same way, so the grid bends without cracking.
+    pub fn displace(mut self, noise: &impl NoiseFn<[f64; 2]>, frequency: f64, amount: f32) -> Self {
+        for point in self.points.iter_mut() {
+            let (x, y) = (point.x as f64 * frequency, point.y as f64 * frequency);
+            *point += vec2(
+                noise.get([x, y]) as f32,
+                noise.get([x + NOISE_OFFSET, y + NOISE_OFFSET]) as f32,
+            ) * amount;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{CellIndex, Grid};
+    use nannou::noise::{OpenSimplex, Seedable};
+    use nannou::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn assert_close(points: &[Vec2], expected: &[Vec2]) {
+        assert_eq!(points.len(), expected.len());
+        for (point, expected) in points.iter().zip(expected.iter()) {
+            assert!(
+                point.distance(*expected) < 1e-4,
+                "{} != {}",
+                point,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn transforms_keep_the_cell_centered() {
+        let rect = geom::Rect::from_x_y_w_h(10.0, 10.0, 20.0, 20.0);
+        let grid = Grid::new(rect, &CellIndex { row: 1, col: 1 });
+        let cell = grid.get(&CellIndex { row: 0, col: 0 }).unwrap();
+        let square = |half: f32| {
+            vec![
+                vec2(10.0 - half, 10.0 - half),
+                vec2(10.0 + half, 10.0 - half),
+                vec2(10.0 + half, 10.0 + half),
+                vec2(10.0 - half, 10.0 + half),
+            ]
+        };
+        assert_close(cell.polygon().points(), &square(10.0));
+        assert_close(cell.polygon().inset(2.0).points(), &square(8.0));
+        assert_close(cell.polygon().outset(2.0).points(), &square(12.0));
+
+        let mut turned = square(10.0);
+        turned.rotate_left(1);
+        assert_close(cell.polygon().rotate(PI / 2.0).points(), &turned);
+        let skewed = cell.polygon().skew(PI / 4.0, 0.0).into_points();
+        assert_close(&skewed[2..], &[vec2(30.0, 20.0), vec2(10.0, 20.0)]);
+        assert_eq!(cell.polygon().subdivide(3).points().len(), 12);
+    }
+
+    #[test]
+    fn deformations_are_reproducible() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = Grid::new(rect, &CellIndex { row: 2, col: 2 });
+        let deform = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let noise = OpenSimplex::new().set_seed(seed as u32);
+            grid.row_major_iter()
+                .map(|cell| {
+                    cell.polygon()
+                        .displace(&noise, 0.05, 5.0)
+                        .jitter(0.1, &mut rng)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(deform(7), deform(7));
+        assert_ne!(deform(7), deform(8));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for cell in grid.row_major_iter() {
+            let jittered = cell.polygon().jitter(0.1, &mut rng);
+            for (point, corner) in jittered.points().iter().zip(cell.polygon().points()) {
+                assert!((*point - *corner).abs().max_element() <= 0.1 * 50.0);
+            }
+        }
+
+        // Shared corners are displaced the same way.
+        let noise = OpenSimplex::new();
+        let bottom_left = grid.get(&CellIndex { row: 1, col: 0 }).unwrap();
+        let bottom_right = grid.get(&CellIndex { row: 1, col: 1 }).unwrap();
+        assert_eq!(
+            bottom_left.polygon().displace(&noise, 0.05, 5.0).points()[1],
+            bottom_right.polygon().displace(&noise, 0.05, 5.0).points()[0]
+        );
+    }
+}
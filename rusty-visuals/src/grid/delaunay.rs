@@ -0,0 +1,495 @@
+/// Delaunay triangulations of point sets: the triangles connecting the points
+/// whose circumcircles hold no other point, which avoids long thin triangles
+/// as much as possible (low poly art, meshes to deform).
+///
+/// API Examples:
+///
+/// let points = poisson_disk_points(bounding_rect, 30.0, model.seed);
+/// let triangulation = Triangulation::new(&points);
+/// for triangle in triangulation.triangles() {
+///     draw.polygon().points(triangulation.corners(triangle)).color(color);
+/// }
+///
+use nannou::prelude::*;
+use std::collections::HashMap;
+
+// Points closer than this are treated as the same point.
+const EPSILON: f32 = 1e-4;
+// The starting triangle's corners are infinitely far from the points in
+// these directions (see Vertex).
+const SUPER_TRIANGLE_DIRECTIONS: [(f64, f64); 3] = [(-1.0, -1.0), (1.0, -1.0), (0.0, 1.0)];
+
+pub struct Triangulation {
+    vertices: Vec<Vec2>,
+    // Indices into vertices, with the corners in counter clockwise order.
+    triangles: Vec<[usize; 3]>,
+    // For every triangle, the triangle across each of its edges. Edge i goes
+    // from corner i to corner i + 1. None for edges on the convex hull.
+    adjacent: Vec<[Option<usize>; 3]>,
+}
+
+impl Triangulation {
+    /// Triangulates the points with the Bowyer-Watson algorithm. The triangles
+    /// index into `points` as given: repeated points only show up once (at
+    /// their first index) and when all the points are on one line there are
+    /// no triangles at all.
+    pub fn new(points: &[Vec2]) -> Self {
+        let triangles = bowyer_watson(points);
+
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (t, triangle) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                edges
+                    .entry(edge_key(triangle[i], triangle[(i + 1) % 3]))
+                    .or_default()
+                    .push(t);
+            }
+        }
+        let adjacent = triangles
+            .iter()
+            .enumerate()
+            .map(|(t, triangle)| {
+                let mut across = [None; 3];
+                for (i, other) in across.iter_mut().enumerate() {
+                    let key = edge_key(triangle[i], triangle[(i + 1) % 3]);
+                    *other = edges[&key].iter().copied().find(|u| *u != t);
+                }
+                across
+            })
+            .collect();
+
+        Triangulation {
+            vertices: points.to_vec(),
+            triangles,
+            adjacent,
+        }
+    }
+
+    /// The points that were triangulated.
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    /// Every triangle as the indices of its corners in counter clockwise order.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// The positions of a triangle's corners.
+    pub fn corners(&self, triangle: &[usize; 3]) -> [Vec2; 3] {
+        [
+            self.vertices[triangle[0]],
+            self.vertices[triangle[1]],
+            self.vertices[triangle[2]],
+        ]
+    }
+
+    /// The triangles sharing an edge with the triangle at the given position:
+    /// the i-th one is across the edge from corner i to corner i + 1, None if
+    /// that edge is on the convex hull.
+    pub fn adjacent_triangles(&self, triangle: usize) -> Option<&[Option<usize>; 3]> {
+        self.adjacent.get(triangle)
+    }
+
+    /// Every edge of the triangulation once, as (smaller index, larger index).
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = self
+            .triangles
+            .iter()
+            .flat_map(|t| (0..3).map(move |i| edge_key(t[i], t[(i + 1) % 3])))
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+
+    /// The vertices connected to the given vertex by an edge.
+    pub fn vertex_neighbors(&self, vertex: usize) -> Vec<usize> {
+        self.edges()
+            .into_iter()
+            .filter_map(|(a, b)| match vertex {
+                v if v == a => Some(b),
+                v if v == b => Some(a),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+// Inserts the points one at a time into a triangulation that starts as one
+// triangle holding all of them. Every triangle whose circumcircle holds the
+// new point is removed and the hole is filled with triangles fanning out
+// from the point. Finally the triangles touching the starting triangle's
+// corners are removed.
+fn bowyer_watson(points: &[Vec2]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return vec![];
+    }
+    let min = points.iter().fold(points[0], |m, p| m.min(*p));
+    let max = points.iter().fold(points[0], |m, p| m.max(*p));
+    let center = (min + max) / 2.0;
+
+    // The starting triangle's corners go after the points.
+    let n = points.len();
+    let vertices: Vec<Vertex> = points
+        .iter()
+        .map(|point| Vertex {
+            position: (point.x as f64, point.y as f64),
+            direction: (0.0, 0.0),
+        })
+        .chain(SUPER_TRIANGLE_DIRECTIONS.iter().map(|direction| Vertex {
+            position: (center.x as f64, center.y as f64),
+            direction: *direction,
+        }))
+        .collect();
+    let mut triangles = vec![Triangle::new(&vertices, [n, n + 1, n + 2])];
+
+    for (p, point) in points.iter().enumerate() {
+        if points[..p].iter().any(|q| q.distance(*point) < EPSILON) {
+            continue;
+        }
+        let (bad, good): (Vec<Triangle>, Vec<Triangle>) = triangles
+            .into_iter()
+            .partition(|t| t.circumcircle_contains(*point));
+
+        // The hole's outline is made of the edges that only one of the
+        // removed triangles has.
+        let mut edge_counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for t in bad.iter() {
+            for edge in t.edges().iter() {
+                *edge_counts.entry(edge_key(edge.0, edge.1)).or_default() += 1;
+            }
+        }
+        triangles = good;
+        for t in bad.iter() {
+            for (a, b) in t.edges().iter() {
+                if edge_counts[&edge_key(*a, *b)] == 1 {
+                    triangles.push(Triangle::new(&vertices, [*a, *b, p]));
+                }
+            }
+        }
+    }
+
+    triangles
+        .into_iter()
+        .filter(|t| t.corners.iter().all(|corner| *corner < n) && !t.is_degenerate())
+        .map(|t| t.corners)
+        .collect()
+}
+
+// A vertex at `position + M * direction` for an arbitrarily large M. The
+// points have no direction and the starting triangle's corners are
+// infinitely far away: a starting triangle that is only very large loses the
+// triangles along the convex hull of nearly collinear points, whose
+// circumcircles reach past its corners.
+#[derive(Clone, Copy)]
+struct Vertex {
+    position: (f64, f64),
+    direction: (f64, f64),
+}
+
+impl Vertex {
+    fn is_finite(&self) -> bool {
+        self.direction == (0.0, 0.0)
+    }
+
+    // The coordinates relative to `origin` as polynomials in M.
+    fn relative_to(&self, origin: (f64, f64)) -> (Polynomial, Polynomial) {
+        (
+            [self.position.0 - origin.0, self.direction.0, 0.0, 0.0, 0.0],
+            [self.position.1 - origin.1, self.direction.1, 0.0, 0.0, 0.0],
+        )
+    }
+}
+
+// The coefficients of a polynomial in M from the constant up to M^4, the
+// highest power the circumcircle test needs.
+type Polynomial = [f64; 5];
+
+fn add(a: Polynomial, b: Polynomial) -> Polynomial {
+    let mut sum = a;
+    for (s, b) in sum.iter_mut().zip(b.iter()) {
+        *s += b;
+    }
+    sum
+}
+
+fn sub(a: Polynomial, b: Polynomial) -> Polynomial {
+    add(a, b.map(|b| -b))
+}
+
+fn mul(a: Polynomial, b: Polynomial) -> Polynomial {
+    let mut product = [0.0; 5];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate().take(5 - i) {
+            product[i + j] += a * b;
+        }
+    }
+    product
+}
+
+// The sign of the polynomial for an arbitrarily large M: the sign of its
+// highest non zero coefficient.
+fn sign(polynomial: Polynomial) -> f64 {
+    polynomial
+        .iter()
+        .rev()
+        .find(|coefficient| **coefficient != 0.0)
+        .map_or(0.0, |coefficient| coefficient.signum())
+}
+
+// Twice the signed area of the triangle abc: positive when the corners are
+// counter clockwise.
+fn orientation(a: Vertex, b: Vertex, c: Vertex) -> Polynomial {
+    let origin = a.position;
+    let (ax, ay) = a.relative_to(origin);
+    let (bx, by) = b.relative_to(origin);
+    let (cx, cy) = c.relative_to(origin);
+    let (abx, aby) = (sub(bx, ax), sub(by, ay));
+    let (acx, acy) = (sub(cx, ax), sub(cy, ay));
+    sub(mul(abx, acy), mul(aby, acx))
+}
+
+struct Triangle {
+    corners: [usize; 3],
+    circumcircle: Circumcircle,
+}
+
+enum Circumcircle {
+    // Computed in f64: nearly flat triangles have huge circumcircles.
+    Finite {
+        center: (f64, f64),
+        radius_squared: f64,
+    },
+    // Points on a line have no circumcircle. Treating it as infinitely
+    // large makes sure the next point inserted replaces the triangle.
+    Degenerate,
+    // The triangle has a corner infinitely far away, so points are tested
+    // against its corners directly.
+    Infinite([Vertex; 3]),
+}
+
+impl Triangle {
+    // Orders the corners counter clockwise.
+    fn new(vertices: &[Vertex], corners: [usize; 3]) -> Self {
+        let [a, b, c] = corners.map(|i| vertices[i]);
+        let cross = sign(orientation(a, b, c));
+        let corners = if cross < 0.0 {
+            [corners[0], corners[2], corners[1]]
+        } else {
+            corners
+        };
+        let circumcircle = if cross == 0.0 {
+            Circumcircle::Degenerate
+        } else if !(a.is_finite() && b.is_finite() && c.is_finite()) {
+            Circumcircle::Infinite(corners.map(|i| vertices[i]))
+        } else {
+            Circumcircle::new(a.position, b.position, c.position)
+        };
+        Triangle {
+            corners,
+            circumcircle,
+        }
+    }
+
+    fn is_degenerate(&self) -> bool {
+        matches!(self.circumcircle, Circumcircle::Degenerate)
+    }
+
+    fn circumcircle_contains(&self, point: Vec2) -> bool {
+        let point = (point.x as f64, point.y as f64);
+        match &self.circumcircle {
+            Circumcircle::Finite {
+                center,
+                radius_squared,
+            } => {
+                let dx = point.0 - center.0;
+                let dy = point.1 - center.1;
+                dx * dx + dy * dy <= *radius_squared
+            }
+            Circumcircle::Degenerate => true,
+            Circumcircle::Infinite(corners) => {
+                // The point is inside the circumcircle of the counter
+                // clockwise triangle abc when this determinant is positive.
+                let [a, b, c] = corners.map(|corner| {
+                    let (x, y) = corner.relative_to(point);
+                    (x, y, add(mul(x, x), mul(y, y)))
+                });
+                let determinant = add(
+                    sub(
+                        mul(a.0, sub(mul(b.1, c.2), mul(b.2, c.1))),
+                        mul(a.1, sub(mul(b.0, c.2), mul(b.2, c.0))),
+                    ),
+                    mul(a.2, sub(mul(b.0, c.1), mul(b.1, c.0))),
+                );
+                sign(determinant) >= 0.0
+            }
+        }
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.corners;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+impl Circumcircle {
+    fn new(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> Self {
+        let d = 2.0 * ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0));
+        if d.abs() < f64::EPSILON {
+            return Circumcircle::Degenerate;
+        }
+        let (a2, b2, c2) = (
+            a.0 * a.0 + a.1 * a.1,
+            b.0 * b.0 + b.1 * b.1,
+            c.0 * c.0 + c.1 * c.1,
+        );
+        let x = (a2 * (b.1 - c.1) + b2 * (c.1 - a.1) + c2 * (a.1 - b.1)) / d;
+        let y = (a2 * (c.0 - b.0) + b2 * (a.0 - c.0) + c2 * (b.0 - a.0)) / d;
+        Circumcircle::Finite {
+            center: (x, y),
+            radius_squared: (a.0 - x).powi(2) + (a.1 - y).powi(2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Triangulation;
+    use crate::grid::random_points;
+    use nannou::prelude::*;
+
+    fn circumcircle_is_empty(triangulation: &Triangulation, triangle: &[usize; 3]) -> bool {
+        let [a, b, c] = triangulation.corners(triangle);
+        // The point is inside the circumcircle of the counter clockwise
+        // triangle abc when this determinant is positive.
+        triangulation.vertices().iter().all(|p| {
+            let relative = |q: Vec2| ((q.x - p.x) as f64, (q.y - p.y) as f64);
+            let (a, b, c) = (relative(a), relative(b), relative(c));
+            let det = (a.0 * a.0 + a.1 * a.1) * (b.0 * c.1 - c.0 * b.1)
+                - (b.0 * b.0 + b.1 * b.1) * (a.0 * c.1 - c.0 * a.1)
+                + (c.0 * c.0 + c.1 * c.1) * (a.0 * b.1 - b.0 * a.1);
+            det < 1e-6
+        })
+    }
+
+    #[test]
+    fn triangles_are_delaunay() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 200.0, 100.0);
+        let triangulation = Triangulation::new(&random_points(rect, 60, 5));
+        assert!(!triangulation.triangles().is_empty());
+        for (t, triangle) in triangulation.triangles().iter().enumerate() {
+            assert!(circumcircle_is_empty(&triangulation, triangle));
+            let [a, b, c] = triangulation.corners(triangle);
+            assert!(
+                (b - a).perp_dot(c - a) > 0.0,
+                "corners are counter clockwise"
+            );
+            for other in triangulation
+                .adjacent_triangles(t)
+                .unwrap()
+                .iter()
+                .flatten()
+            {
+                assert!(triangulation
+                    .adjacent_triangles(*other)
+                    .unwrap()
+                    .contains(&Some(t)));
+            }
+        }
+        // Euler's formula for a triangulated point set: V - E + F = 2 with
+        // the outside counted as a face.
+        let (v, e, f) = (
+            60,
+            triangulation.edges().len(),
+            triangulation.triangles().len(),
+        );
+        assert_eq!(v + f + 1, e + 2);
+    }
+
+    #[test]
+    fn square_with_a_center_point() {
+        let points = vec![
+            vec2(0.0, 0.0),
+            vec2(-1.0, -1.0),
+            vec2(1.0, -1.0),
+            vec2(1.0, 1.0),
+            vec2(-1.0, 1.0),
+        ];
+        let triangulation = Triangulation::new(&points);
+        assert_eq!(triangulation.triangles().len(), 4);
+        assert_eq!(triangulation.edges().len(), 8);
+        assert_eq!(triangulation.vertex_neighbors(0), vec![1, 2, 3, 4]);
+        assert_eq!(triangulation.vertex_neighbors(1), vec![0, 2, 4]);
+        for t in 0..4 {
+            let adjacent = triangulation.adjacent_triangles(t).unwrap();
+            assert_eq!(
+                adjacent.iter().filter(|a| a.is_none()).count(),
+                1,
+                "every triangle has one edge on the outline of the square"
+            );
+        }
+    }
+
+    #[test]
+    fn nearly_collinear_points_keep_their_hull() {
+        // Points on a very flat arc are all on the convex hull, so the n - 2
+        // triangles have to cover the polygon the points outline.
+        let points: Vec<Vec2> = (0..40)
+            .map(|i| vec2(i as f32 * 10.0, -0.001 * (i as f32 - 20.0).powi(2)))
+            .collect();
+        let triangulation = Triangulation::new(&points);
+        assert_eq!(triangulation.triangles().len(), points.len() - 2);
+        let area = |[a, b, c]: [Vec2; 3]| (b - a).perp_dot(c - a) as f64 / 2.0;
+        let covered: f64 = triangulation
+            .triangles()
+            .iter()
+            .map(|triangle| area(triangulation.corners(triangle)))
+            .sum();
+        let outline: f64 = points
+            .windows(2)
+            .map(|pair| area([points[0], pair[1], pair[0]]))
+            .sum();
+        assert!((covered - outline).abs() < 1e-3);
+    }
+
+    #[test]
+    fn degenerate_points() {
+        let collinear: Vec<Vec2> = (0..10).map(|i| vec2(i as f32, 2.0 * i as f32)).collect();
+        assert!(Triangulation::new(&collinear).triangles().is_empty());
+        assert!(Triangulation::new(&[vec2(0.0, 0.0), vec2(1.0, 0.0)])
+            .triangles()
+            .is_empty());
+
+        let points = vec![
+            vec2(0.0, 0.0),
+            vec2(4.0, 0.0),
+            vec2(0.0, 0.0),
+            vec2(0.0, 3.0),
+            vec2(4.0, 0.0),
+        ];
+        let triangulation = Triangulation::new(&points);
+        assert_eq!(triangulation.triangles().len(), 1);
+        let mut corners = triangulation.triangles()[0].to_vec();
+        corners.sort_unstable();
+        assert_eq!(
+            corners,
+            vec![0, 1, 3],
+            "repeated points use their first index"
+        );
+        assert_eq!(triangulation.vertices().len(), 5);
+
+        let mut points = collinear;
+        points.push(vec2(5.0, 0.0));
+        let triangulation = Triangulation::new(&points);
+        assert_eq!(triangulation.triangles().len(), 9);
+        for triangle in triangulation.triangles() {
+            let [a, b, c] = triangulation.corners(triangle);
+            assert!((b - a).perp_dot(c - a) > 0.0, "no flat triangles");
+        }
+    }
+}
@@ -0,0 +1,314 @@
+/// Hexagonal grids. The math follows https://www.redblobgames.com/grids/hexagons/
+///
+/// API Examples:
+///
+/// let grid = HexGrid::new(
+///     bounding_rect,
+///     &CellIndex { row, col },
+///     HexOrientation::PointyTop,
+/// );
+/// for hex in grid.row_major_iter() {
+///     draw.polygon().points(hex.corners).color(colorer.color(grid.grid_params(hex)));
+/// }
+///
+use crate::colorer::GridParams;
+use crate::grid::{Cell, CellIndex, RowMajorIterator};
+use nannou::prelude::*;
+
+const SQRT_3: f32 = 1.732_050_8;
+
+/// HexOrientation decides which way the hexagons in a HexGrid point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexOrientation {
+    /// Hexagons have a corner at the top and are laid out in rows. Odd rows
+    /// are shoved right by half a hexagon ("odd-r" offset coordinates).
+    PointyTop,
+    /// Hexagons have a flat edge at the top and are laid out in columns. Odd
+    /// columns are shoved down by half a hexagon ("odd-q" offset coordinates).
+    FlatTop,
+}
+
+/// AxialIndex is the (q, r) axial coordinate of a hexagon. Unlike the offset
+/// (row, col) index, neighbors and distances are simple arithmetic in axial
+/// coordinates.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct AxialIndex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl AxialIndex {
+    /// The implicit third cube coordinate (q + r + s = 0).
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    /// Number of hexagon steps between two hexagons.
+    pub fn distance(&self, other: &AxialIndex) -> i32 {
+        ((self.q - other.q).abs() + (self.r - other.r).abs() + (self.s() - other.s()).abs()) / 2
+    }
+}
+
+pub struct HexCell {
+    // The bounding box of the hexagon along with its offset (row, column)
+    // index. This is what gets handed to colorers, so anything that colors
+    // a Grid can also color a HexGrid.
+    pub cell: Cell,
+    pub axial: AxialIndex,
+    // The corners of the hexagon in counter clockwise order.
+    pub corners: [Vec2; 6],
+}
+
+impl HexCell {
+    pub fn center(&self) -> Vec2 {
+        self.cell.xy
+    }
+}
+
+// A HexGrid is a collection of HexCells stored in offset (row, column)
+// order so that it can be walked in the same row major order as a Grid.
+pub struct HexGrid {
+    cells: Vec<Vec<HexCell>>,
+    rect: Rect,
+    num_cells: CellIndex,
+    orientation: HexOrientation,
+    // Distance from the center of a hexagon to any of its corners.
+    size: f32,
+}
+
+impl HexGrid {
+    /// num_cells: (number of rows, number of columns)
+    /// The hexagons are kept regular, so the grid is scaled to the largest
+    /// size that fits inside the bounding_rect and then centered in it.
+    pub fn new(bounding_rect: Rect, num_cells: &CellIndex, orientation: HexOrientation) -> Self {
+        let rows = num_cells.row as f32;
+        let cols = num_cells.col as f32;
+
+        // Width and height of the whole lattice measured in units of the hexagon size.
+        // Staggering only adds half a hexagon when there is something to stagger.
+        let lattice_units = match orientation {
+            HexOrientation::PointyTop => {
+                let stagger = if num_cells.row > 1 { 0.5 } else { 0.0 };
+                vec2(SQRT_3 * (cols + stagger), 1.5 * (rows - 1.0) + 2.0)
+            }
+            HexOrientation::FlatTop => {
+                let stagger = if num_cells.col > 1 { 0.5 } else { 0.0 };
+                vec2(1.5 * (cols - 1.0) + 2.0, SQRT_3 * (rows + stagger))
+            }
+        };
+        let size = (bounding_rect.w() / lattice_units.x).min(bounding_rect.h() / lattice_units.y);
+        let lattice = Rect::from_xy_wh(bounding_rect.xy(), lattice_units * size);
+
+        let cells = (0..num_cells.row)
+            .map(|row| {
+                (0..num_cells.col)
+                    .map(|col| {
+                        let index = CellIndex { row, col };
+                        let xy = hex_center(&lattice, &index, orientation, size);
+                        HexCell {
+                            cell: Cell::new(hex_wh(orientation, size), xy, index),
+                            axial: offset_to_axial(&index, orientation),
+                            corners: hex_corners(xy, orientation, size),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        HexGrid {
+            cells,
+            rect: bounding_rect,
+            num_cells: *num_cells,
+            orientation,
+            size,
+        }
+    }
+
+    /// The row major iterator will traverse from the top left of the
+    /// grid down to the bottom right going hexagon by hexagon in each row.
+    pub fn row_major_iter(&self) -> RowMajorIterator<'_, HexCell> {
+        RowMajorIterator::new(&self.cells)
+    }
+
+    pub fn get(&self, index: &CellIndex) -> Option<&HexCell> {
+        self.cells.get(index.row).and_then(|r| r.get(index.col))
+    }
+
+    pub fn get_by_axial(&self, axial: &AxialIndex) -> Option<&HexCell> {
+        axial_to_offset(axial, self.orientation).and_then(|index| self.get(&index))
+    }
+
+    /// The six hexagons sharing an edge with the given hexagon that are
+    /// inside the grid.
+    pub fn neighbors(&self, axial: &AxialIndex) -> Vec<&HexCell> {
+        const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+        DIRECTIONS
+            .iter()
+            .filter_map(|(dq, dr)| {
+                self.get_by_axial(&AxialIndex {
+                    q: axial.q + dq,
+                    r: axial.r + dr,
+                })
+            })
+            .collect()
+    }
+
+    /// GridParams for a hexagon in this grid so that any GridColorer can
+    /// color it.
+    pub fn grid_params<'a>(&'a self, hex: &'a HexCell) -> GridParams<'a> {
+        GridParams {
+            cell: &hex.cell,
+            total_num_cells: &self.num_cells,
+        }
+    }
+
+    pub fn num_cells(&self) -> &CellIndex {
+        &self.num_cells
+    }
+
+    pub fn orientation(&self) -> HexOrientation {
+        self.orientation
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    pub fn wh(&self) -> Vec2 {
+        self.rect.wh()
+    }
+
+    pub fn xy(&self) -> Vec2 {
+        self.rect.xy()
+    }
+}
+
+fn hex_wh(orientation: HexOrientation, size: f32) -> Vec2 {
+    match orientation {
+        HexOrientation::PointyTop => vec2(SQRT_3 * size, 2.0 * size),
+        HexOrientation::FlatTop => vec2(2.0 * size, SQRT_3 * size),
+    }
+}
+
+// Anchor from the top left of the lattice and build downwards and to the
+// right, the same way Grid does. Odd rows (pointy) or odd columns (flat)
+// are staggered by half a hexagon.
+fn hex_center(lattice: &Rect, index: &CellIndex, orientation: HexOrientation, size: f32) -> Vec2 {
+    let row = index.row as f32;
+    let col = index.col as f32;
+    match orientation {
+        HexOrientation::PointyTop => {
+            let stagger = if index.row.is_multiple_of(2) {
+                0.0
+            } else {
+                0.5
+            };
+            vec2(
+                lattice.left() + SQRT_3 * size * (col + 0.5 + stagger),
+                lattice.top() - size - 1.5 * size * row,
+            )
+        }
+        HexOrientation::FlatTop => {
+            let stagger = if index.col.is_multiple_of(2) {
+                0.0
+            } else {
+                0.5
+            };
+            vec2(
+                lattice.left() + size + 1.5 * size * col,
+                lattice.top() - SQRT_3 * size * (row + 0.5 + stagger),
+            )
+        }
+    }
+}
+
+fn hex_corners(center: Vec2, orientation: HexOrientation, size: f32) -> [Vec2; 6] {
+    let start_angle = match orientation {
+        HexOrientation::PointyTop => -30.0,
+        HexOrientation::FlatTop => 0.0,
+    };
+    let mut corners = [Vec2::ZERO; 6];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let angle = deg_to_rad(start_angle + 60.0 * i as f32);
+        *corner = center + vec2(angle.cos(), angle.sin()) * size;
+    }
+    corners
+}
+
+fn offset_to_axial(index: &CellIndex, orientation: HexOrientation) -> AxialIndex {
+    let row = index.row as i32;
+    let col = index.col as i32;
+    match orientation {
+        HexOrientation::PointyTop => AxialIndex {
+            q: col - (row - (row & 1)) / 2,
+            r: row,
+        },
+        HexOrientation::FlatTop => AxialIndex {
+            q: col,
+            r: row - (col - (col & 1)) / 2,
+        },
+    }
+}
+
+fn axial_to_offset(axial: &AxialIndex, orientation: HexOrientation) -> Option<CellIndex> {
+    let (row, col) = match orientation {
+        HexOrientation::PointyTop => (axial.r, axial.q + (axial.r - (axial.r & 1)) / 2),
+        HexOrientation::FlatTop => (axial.r + (axial.q - (axial.q & 1)) / 2, axial.q),
+    };
+    if row < 0 || col < 0 {
+        return None;
+    }
+    Some(CellIndex {
+        row: row as usize,
+        col: col as usize,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AxialIndex, HexGrid, HexOrientation};
+    use crate::grid::CellIndex;
+    use nannou::prelude::*;
+
+    #[test]
+    fn hexagons_fit_inside_the_bounding_rect() {
+        let rect = geom::Rect::from_x_y_w_h(10.0, -10.0, 300.0, 200.0);
+        for orientation in [HexOrientation::PointyTop, HexOrientation::FlatTop].iter() {
+            let grid = HexGrid::new(rect, &CellIndex { row: 6, col: 9 }, *orientation);
+            assert_eq!(grid.row_major_iter().count(), 54);
+            for hex in grid.row_major_iter() {
+                for corner in hex.corners.iter() {
+                    assert!(corner.x >= rect.left() - 0.001 && corner.x <= rect.right() + 0.001);
+                    assert!(corner.y >= rect.bottom() - 0.001 && corner.y <= rect.top() + 0.001);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn axial_and_offset_indices_round_trip() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        for orientation in [HexOrientation::PointyTop, HexOrientation::FlatTop].iter() {
+            let grid = HexGrid::new(rect, &CellIndex { row: 5, col: 5 }, *orientation);
+            for hex in grid.row_major_iter() {
+                let found = grid.get_by_axial(&hex.axial).unwrap();
+                assert_eq!(found.cell.index, hex.cell.index);
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_are_one_step_away() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = HexGrid::new(rect, &CellIndex { row: 5, col: 5 }, HexOrientation::PointyTop);
+        let center = grid.get(&CellIndex { row: 2, col: 2 }).unwrap();
+        let neighbors = grid.neighbors(&center.axial);
+        assert_eq!(neighbors.len(), 6);
+        for neighbor in neighbors {
+            assert_eq!(neighbor.axial.distance(&center.axial), 1);
+            let distance = neighbor.center().distance(center.center());
+            assert!((distance - 3.0_f32.sqrt() * grid.size()).abs() < 0.001);
+        }
+        assert_eq!(grid.neighbors(&AxialIndex { q: 0, r: 0 }).len(), 2);
+    }
+}
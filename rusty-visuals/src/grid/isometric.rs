@@ -0,0 +1,222 @@
+/// Isometric grids of diamond shaped tiles. Row 0, column 0 is the tile at the
+/// top of the diamond, columns run down and to the right and rows run down
+/// and to the left.
+///
+/// API Examples:
+///
+/// let grid = IsometricGrid::new(bounding_rect, &CellIndex { row, col });
+/// for tile in grid.row_major_iter() {
+///     draw.polygon().points(tile.points).color(colorer.color(grid.grid_params(tile)));
+/// }
+///
+use crate::colorer::GridParams;
+use crate::grid::{Cell, CellIndex, RowMajorIterator};
+use nannou::prelude::*;
+
+// Height of a tile divided by its width. 0.5 gives the classic 2:1 pixel
+// art isometric tile.
+const TILE_ASPECT_RATIO: f32 = 0.5;
+
+pub struct IsometricCell {
+    // The bounding box of the diamond along with its (row, column) index.
+    pub cell: Cell,
+    // The right, top, left and bottom corners of the diamond, in that
+    // (counter clockwise) order.
+    pub points: [Vec2; 4],
+}
+
+pub struct IsometricGrid {
+    cells: Vec<Vec<IsometricCell>>,
+    rect: Rect,
+    num_cells: CellIndex,
+    tile_wh: Vec2,
+    // The top corner of the diamond.
+    top: Vec2,
+}
+
+impl IsometricGrid {
+    /// num_cells: (number of rows, number of columns)
+    /// The tiles keep their aspect ratio, so the grid is scaled to the
+    /// largest size that fits inside the bounding_rect and then centered in it.
+    pub fn new(bounding_rect: Rect, num_cells: &CellIndex) -> Self {
+        // Every row or column adds half a tile to both the width and the
+        // height of the whole diamond.
+        let half_tiles = (num_cells.row + num_cells.col) as f32 / 2.0;
+        let tile_w = (bounding_rect.w() / half_tiles)
+            .min(bounding_rect.h() / (half_tiles * TILE_ASPECT_RATIO));
+        let tile_wh = vec2(tile_w, tile_w * TILE_ASPECT_RATIO);
+        let lattice = Rect::from_xy_wh(bounding_rect.xy(), tile_wh * half_tiles);
+
+        // The top corner of the diamond sits above the left edge shifted
+        // right by one half tile for every row.
+        let top = vec2(
+            lattice.left() + num_cells.row as f32 * tile_wh.x / 2.0,
+            lattice.top(),
+        );
+        let cells = (0..num_cells.row)
+            .map(|row| {
+                (0..num_cells.col)
+                    .map(|col| {
+                        let xy = vec2(
+                            top.x + (col as f32 - row as f32) * tile_wh.x / 2.0,
+                            top.y - (col as f32 + row as f32 + 1.0) * tile_wh.y / 2.0,
+                        );
+                        IsometricCell {
+                            cell: Cell::new(tile_wh, xy, CellIndex { row, col }),
+                            points: [
+                                xy + vec2(tile_wh.x / 2.0, 0.0),
+                                xy + vec2(0.0, tile_wh.y / 2.0),
+                                xy - vec2(tile_wh.x / 2.0, 0.0),
+                                xy - vec2(0.0, tile_wh.y / 2.0),
+                            ],
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        IsometricGrid {
+            cells,
+            rect: bounding_rect,
+            num_cells: *num_cells,
+            tile_wh,
+            top,
+        }
+    }
+
+    /// The row major iterator will traverse from the top of the diamond
+    /// going tile by tile down each row.
+    pub fn row_major_iter(&self) -> RowMajorIterator<'_, IsometricCell> {
+        RowMajorIterator::new(&self.cells)
+    }
+
+    pub fn get(&self, index: &CellIndex) -> Option<&IsometricCell> {
+        self.cells.get(index.row).and_then(|r| r.get(index.col))
+    }
+
+    /// Finds the tile containing a point in world coordinates. Returns None
+    /// for points outside of the diamond.
+    pub fn cell_at(&self, point: Vec2) -> Option<&IsometricCell> {
+        // Measured in half tiles from the top corner, a tile's diamond is the
+        // unit square at (col, row) once the axes are turned by 45 degrees.
+        let across = (point.x - self.top.x) / (self.tile_wh.x / 2.0);
+        let down = (self.top.y - point.y) / (self.tile_wh.y / 2.0);
+        let col = (down + across) / 2.0;
+        let row = (down - across) / 2.0;
+        if col < 0.0 || row < 0.0 {
+            return None;
+        }
+        self.get(&CellIndex {
+            row: row as usize,
+            col: col as usize,
+        })
+    }
+
+    /// The (up to) four tiles that share an edge with the tile at the given index.
+    pub fn neighbors(&self, index: &CellIndex) -> Vec<&IsometricCell> {
+        let mut indices = vec![
+            CellIndex {
+                row: index.row + 1,
+                col: index.col,
+            },
+            CellIndex {
+                row: index.row,
+                col: index.col + 1,
+            },
+        ];
+        if index.row > 0 {
+            indices.push(CellIndex {
+                row: index.row - 1,
+                col: index.col,
+            });
+        }
+        if index.col > 0 {
+            indices.push(CellIndex {
+                row: index.row,
+                col: index.col - 1,
+            });
+        }
+        indices.iter().filter_map(|i| self.get(i)).collect()
+    }
+
+    /// GridParams for a tile in this grid so that any GridColorer can color it.
+    pub fn grid_params<'a>(&'a self, tile: &'a IsometricCell) -> GridParams<'a> {
+        GridParams {
+            cell: &tile.cell,
+            total_num_cells: &self.num_cells,
+        }
+    }
+
+    pub fn num_cells(&self) -> &CellIndex {
+        &self.num_cells
+    }
+
+    pub fn tile_wh(&self) -> Vec2 {
+        self.tile_wh
+    }
+
+    pub fn wh(&self) -> Vec2 {
+        self.rect.wh()
+    }
+
+    pub fn xy(&self) -> Vec2 {
+        self.rect.xy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IsometricGrid;
+    use crate::grid::CellIndex;
+    use nannou::prelude::*;
+
+    #[test]
+    fn tiles_are_laid_out_in_a_diamond() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 200.0, 100.0);
+        let grid = IsometricGrid::new(rect, &CellIndex { row: 4, col: 4 });
+        assert_eq!(grid.tile_wh(), vec2(50.0, 25.0));
+        let top = grid.get(&CellIndex { row: 0, col: 0 }).unwrap();
+        assert_eq!(top.cell.xy, vec2(0.0, 37.5));
+        // Columns run down and to the right, rows down and to the left.
+        let right = grid.get(&CellIndex { row: 0, col: 1 }).unwrap();
+        assert_eq!(right.cell.xy, vec2(25.0, 25.0));
+        let left = grid.get(&CellIndex { row: 1, col: 0 }).unwrap();
+        assert_eq!(left.cell.xy, vec2(-25.0, 25.0));
+    }
+
+    #[test]
+    fn cell_at_finds_every_tile() {
+        let rect = geom::Rect::from_x_y_w_h(10.0, -20.0, 300.0, 200.0);
+        let grid = IsometricGrid::new(rect, &CellIndex { row: 3, col: 5 });
+        for tile in grid.row_major_iter() {
+            let index = tile.cell.index;
+            assert_eq!(grid.cell_at(tile.cell.xy).unwrap().cell.index, index);
+            // Just inside the diamond's corners is still the same tile.
+            for corner in tile.points.iter() {
+                let inside = tile.cell.xy.lerp(*corner, 0.9);
+                assert_eq!(grid.cell_at(inside).unwrap().cell.index, index);
+            }
+        }
+        assert!(grid.cell_at(rect.top_left()).is_none());
+        assert!(grid.cell_at(rect.bottom_right()).is_none());
+    }
+
+    #[test]
+    fn neighbors_share_an_edge() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = IsometricGrid::new(rect, &CellIndex { row: 3, col: 4 });
+        for tile in grid.row_major_iter() {
+            for neighbor in grid.neighbors(&tile.cell.index) {
+                let shared = tile
+                    .points
+                    .iter()
+                    .filter(|p| neighbor.points.iter().any(|q| p.distance(*q) < 0.001))
+                    .count();
+                assert_eq!(shared, 2, "neighbors should share exactly two corners");
+            }
+        }
+        assert_eq!(grid.neighbors(&CellIndex { row: 0, col: 0 }).len(), 2);
+        assert_eq!(grid.neighbors(&CellIndex { row: 1, col: 1 }).len(), 4);
+        assert_eq!(grid.neighbors(&CellIndex { row: 2, col: 3 }).len(), 2);
+    }
+}
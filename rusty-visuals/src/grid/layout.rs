@@ -0,0 +1,229 @@
+/// GridLayout describes how a Grid divides its bounding rect into rows and
+/// columns of (possibly) different sizes.
+///
+/// API Examples:
+///
+/// let layout = GridLayout::weighted(fibonacci_weights(6), vec![1.0; 4])
+///     .gutter(vec2(10.0, 10.0))
+///     .margin(40.0);
+/// let grid = Grid::with_layout(bounding_rect, &layout);
+///
+use crate::grid::CellIndex;
+use nannou::prelude::*;
+
+const GOLDEN_RATIO: f32 = 1.618_034;
+// Upper bound on the buckets indexing the tracks of weighted layouts, for
+// layouts with a few tracks that are tiny next to the others.
+const MAX_BUCKETS: usize = 1 << 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridLayout {
+    // Relative heights of the rows from top to bottom and widths of the
+    // columns from left to right. Only the ratios between the weights matter.
+    pub row_weights: Vec<f32>,
+    pub col_weights: Vec<f32>,
+    // Space between neighboring columns (x) and rows (y).
+    pub gutter: Vec2,
+    // Space between the bounding rect and the outermost cells.
+    pub margin: f32,
+}
+
+impl GridLayout {
+    /// Every row and every column gets the same size. This is the layout used
+    /// by Grid::new.
+    pub fn uniform(num_cells: &CellIndex) -> Self {
+        GridLayout::weighted(vec![1.0; num_cells.row], vec![1.0; num_cells.col])
+    }
+
+    /// Weights must be finite and at least 0, and the weights of the rows
+    /// (and of the columns) must add up to more than 0. Grid::with_layout
+    /// panics otherwise. Tracks with a weight of 0 take no space.
+    pub fn weighted(row_weights: Vec<f32>, col_weights: Vec<f32>) -> Self {
+        GridLayout {
+            row_weights,
+            col_weights,
+            gutter: vec2(0.0, 0.0),
+            margin: 0.0,
+        }
+    }
+
+    pub fn gutter(mut self, gutter: Vec2) -> Self {
+        self.gutter = gutter;
+        self
+    }
+
+    pub fn margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// (number of rows, number of columns)
+    pub fn num_cells(&self) -> CellIndex {
+        CellIndex {
+            row: self.row_weights.len(),
+            col: self.col_weights.len(),
+        }
+    }
+}
+
+/// Tracks are the rows or the columns of a Grid: `length` split into one
+/// track per weight with `gutter` between neighboring tracks.
+#[derive(Clone)]
+pub(crate) enum Tracks {
+    // Every track has the same size, so spans are computed and tracks are
+    // found by dividing instead of being stored and searched.
+    Uniform {
+        count: usize,
+        size: f32,
+        gutter: f32,
+    },
+    // The (offset from the start, size) of every track. The length is cut
+    // into buckets of `bucket_size` and `buckets` holds the track each one
+    // starts in. Buckets are no longer than the distance between two track
+    // starts, so at most one track starts inside any of them.
+    Weighted {
+        spans: Vec<(f32, f32)>,
+        bucket_size: f32,
+        buckets: Vec<usize>,
+    },
+}
+
+impl Tracks {
+    pub(crate) fn uniform(count: usize, length: f32, gutter: f32) -> Self {
+        let available = length - gutter * count.saturating_sub(1) as f32;
+        Tracks::Uniform {
+            count,
+            size: available / count.max(1) as f32,
+            gutter,
+        }
+    }
+
+    pub(crate) fn new(weights: &[f32], length: f32, gutter: f32) -> Self {
+        if weights.is_empty() {
+            return Tracks::uniform(0, length, gutter);
+        }
+        let total_weight: f32 = weights.iter().sum();
+        let is_valid = |weight: &f32| *weight >= 0.0 && weight.is_finite();
+        if !weights.iter().all(is_valid) || total_weight <= 0.0 {
+            panic!("grid layout weights must be finite, at least 0 and add up to more than 0");
+        }
+        if weights.iter().all(|weight| *weight == weights[0]) {
+            return Tracks::uniform(weights.len(), length, gutter);
+        }
+        let available = length - gutter * (weights.len() - 1) as f32;
+
+        let mut offset = 0.0;
+        let spans: Vec<(f32, f32)> = weights
+            .iter()
+            .map(|weight| {
+                let size = available * weight / total_weight;
+                let span = (offset, size);
+                offset += size + gutter;
+                span
+            })
+            .collect();
+
+        let (last_offset, last_size) = spans[spans.len() - 1];
+        let end = last_offset + last_size;
+        let shortest_step = spans
+            .windows(2)
+            .map(|pair| pair[1].0 - pair[0].0)
+            .filter(|step| *step > 0.0)
+            .fold(end, f32::min);
+        let num_buckets = ((end / shortest_step).ceil() as usize).clamp(1, MAX_BUCKETS);
+        let bucket_size = end / num_buckets as f32;
+        let mut track = 0;
+        let buckets = (0..num_buckets)
+            .map(|bucket| {
+                let bucket_start = bucket as f32 * bucket_size;
+                while track + 1 < spans.len() && spans[track + 1].0 <= bucket_start {
+                    track += 1;
+                }
+                track
+            })
+            .collect();
+        Tracks::Weighted {
+            spans,
+            bucket_size,
+            buckets,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Tracks::Uniform { count, .. } => *count,
+            Tracks::Weighted { spans, .. } => spans.len(),
+        }
+    }
+
+    /// The (offset from the start, size) of a track.
+    pub(crate) fn span(&self, track: usize) -> Option<(f32, f32)> {
+        match self {
+            Tracks::Uniform { count, .. } if track >= *count => None,
+            Tracks::Uniform { size, gutter, .. } => Some((track as f32 * (size + gutter), *size)),
+            Tracks::Weighted { spans, .. } => spans.get(track).copied(),
+        }
+    }
+
+    /// The track that starts at or before `offset`, or None if `offset` is
+    /// outside of the tracks. Points in a gutter belong to the track before it.
+    pub(crate) fn find(&self, offset: f32) -> Option<usize> {
+        let (last_offset, last_size) = self.span(self.len().checked_sub(1)?)?;
+        if offset < 0.0 || offset > last_offset + last_size {
+            return None;
+        }
+        match self {
+            Tracks::Uniform {
+                count,
+                size,
+                gutter,
+            } => {
+                let mut track = ((offset / (size + gutter)) as usize).min(count - 1);
+                // Rounding can land one track short when offset is right on
+                // a boundary.
+                if track + 1 < *count && (track + 1) as f32 * (size + gutter) <= offset {
+                    track += 1;
+                }
+                Some(track)
+            }
+            Tracks::Weighted {
+                spans,
+                bucket_size,
+                buckets,
+            } => {
+                let bucket = ((offset / bucket_size) as usize).min(buckets.len() - 1);
+                // Only the tracks starting inside the bucket can be past its
+                // first track: one at most, or a few empty (zero weight) ones.
+                let mut track = buckets[bucket];
+                // Rounding can land one bucket past an offset right on a
+                // bucket boundary.
+                while track > 0 && spans[track].0 > offset {
+                    track -= 1;
+                }
+                while track + 1 < spans.len() && spans[track + 1].0 <= offset {
+                    track += 1;
+                }
+                Some(track)
+            }
+        }
+    }
+}
+
+/// [1, 1/φ, 1/φ², ...]: every track is the golden ratio smaller than the one
+/// before it.
+pub fn golden_ratio_weights(n: usize) -> Vec<f32> {
+    (0..n).map(|i| GOLDEN_RATIO.powi(-(i as i32))).collect()
+}
+
+/// [1, 1, 2, 3, 5, ...]
+pub fn fibonacci_weights(n: usize) -> Vec<f32> {
+    let mut weights = Vec::with_capacity(n);
+    let (mut a, mut b) = (1.0, 1.0);
+    for _ in 0..n {
+        weights.push(a);
+        let next = a + b;
+        a = b;
+        b = next;
+    }
+    weights
+}
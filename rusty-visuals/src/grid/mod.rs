@@ -0,0 +1,835 @@
+/// Inspired by https://github.com/gridbugs/grid-2d/blob/master/src/lib.rs
+///
+/// API Examples:
+///
+/// let grid = Grid::new(
+///     (width, height),
+///     (x, y),
+///     (x_tile_count, y_tile_count),
+/// );
+/// for cell in grid.row_major_iter() {...}
+///
+use nannou::prelude::*;
+use std::collections::HashMap;
+
+mod data;
+pub use data::{DataIterator, DataIteratorMut};
+
+mod delaunay;
+pub use delaunay::Triangulation;
+
+mod deform;
+pub use deform::CellPolygon;
+
+mod hex;
+pub use hex::*;
+
+mod isometric;
+pub use isometric::*;
+
+mod layout;
+use layout::Tracks;
+pub use layout::{fibonacci_weights, golden_ratio_weights, GridLayout};
+
+mod neighborhood;
+pub use neighborhood::EdgeMode;
+
+mod sampling;
+pub use sampling::{poisson_disk_points, random_points};
+
+mod subdivision;
+pub use subdivision::*;
+
+mod traversal;
+pub use traversal::OrderedGridIterator;
+
+mod triangle;
+pub use triangle::*;
+
+mod voronoi;
+pub use voronoi::*;
+
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct CellIndex {
+    pub row: usize,
+    pub col: usize,
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    // Note that the x, y coordinates here are the center coordinates of
+    // the cell. Use the `left()` or `bottom()` methods to get the appropriate
+    // coordinates.
+    pub xy: Vec2,
+    pub wh: Vec2,
+
+    // index is the (i,j) row, column index that this cell
+    // represents.
+    pub index: CellIndex,
+
+    // span is the number of (rows, columns) this cell covers. It is
+    // (1, 1) unless the cell was merged with its neighbors by Grid::merge.
+    pub span: CellIndex,
+}
+
+/// Cell implements functions for a Grid container struct
+/// that represents a single block on the Grid.
+impl Cell {
+    // Width / Height; Coordinates; Index in 2D matix
+    fn new(wh: Vec2, xy: Vec2, index: CellIndex) -> Self {
+        Cell {
+            wh,
+            xy,
+            index,
+            span: CellIndex { row: 1, col: 1 },
+        }
+    }
+
+    pub fn left(&self) -> f32 {
+        self.xy.x - (self.wh.x / 2.0)
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.xy.y - (self.wh.y / 2.0)
+    }
+
+    pub fn top(&self) -> f32 {
+        self.xy.y + (self.wh.y / 2.0)
+    }
+
+    pub fn right(&self) -> f32 {
+        self.xy.x + (self.wh.x / 2.0)
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.left()
+            && point.x <= self.right()
+            && point.y >= self.bottom()
+            && point.y <= self.top()
+    }
+}
+
+// A Grid is a 2D matrix of Cells. The cells are not stored: every cell is
+// computed from its index and the sizes of the rows and columns each time it
+// is looked up or iterated over, so the cells never take up any memory.
+// A Grid<T> also stores a T for every cell (see Grid::map).
+pub struct Grid<T = ()> {
+    rect: Rect,
+    // The bounding rect minus the layout's margin.
+    inner_rect: Rect,
+    row_tracks: Tracks,
+    col_tracks: Tracks,
+    num_cells: CellIndex,
+    edge_mode: EdgeMode,
+    // Maps the index of every cell absorbed by a merge to the index of the
+    // cell that owns the merged block.
+    merged: HashMap<CellIndex, CellIndex>,
+    // Maps the index of the cell owning a merged block to the block's span.
+    spans: HashMap<CellIndex, CellIndex>,
+    // One value per (row, column) index in row major order.
+    data: Vec<T>,
+}
+
+impl Grid {
+    /// num_cells: (number of rows, number of columns)
+    /// NOTE: All the indexing and dimensions follow the matrix
+    /// indexing scheme (i.e (rows, columns)).
+    pub fn new(bounding_rect: Rect, num_cells: &CellIndex) -> Self {
+        Grid::from_tracks(
+            bounding_rect,
+            bounding_rect,
+            Tracks::uniform(num_cells.row, bounding_rect.h(), 0.0),
+            Tracks::uniform(num_cells.col, bounding_rect.w(), 0.0),
+        )
+    }
+
+    /// Builds a Grid whose rows and columns are sized by the weights of the
+    /// layout, separated by its gutters and inset from the bounding rect by
+    /// its margin. Panics if the weights are not valid (see
+    /// GridLayout::weighted).
+    pub fn with_layout(bounding_rect: Rect, layout: &GridLayout) -> Self {
+        let inner_rect = bounding_rect.pad(layout.margin);
+        let row_tracks = Tracks::new(&layout.row_weights, inner_rect.h(), layout.gutter.y);
+        let col_tracks = Tracks::new(&layout.col_weights, inner_rect.w(), layout.gutter.x);
+        Grid::from_tracks(bounding_rect, inner_rect, row_tracks, col_tracks)
+    }
+
+    fn from_tracks(rect: Rect, inner_rect: Rect, row_tracks: Tracks, col_tracks: Tracks) -> Self {
+        let num_cells = CellIndex {
+            row: row_tracks.len(),
+            col: col_tracks.len(),
+        };
+        Grid {
+            rect,
+            inner_rect,
+            num_cells,
+            row_tracks,
+            col_tracks,
+            edge_mode: EdgeMode::Bounded,
+            merged: HashMap::new(),
+            spans: HashMap::new(),
+            // A Vec of () never allocates.
+            data: vec![(); num_cells.row * num_cells.col],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// The row major iterator will traverse from the
+    /// top left of the matrix down to the bottom right
+    /// going cell by cell in each row.
+    pub fn row_major_iter(&self) -> GridIterator<'_> {
+        GridIterator::new(self.geometry())
+    }
+
+    /// Merges the block of cells starting at `top_left` and covering `span`
+    /// (rows, columns) into one cell. The cell at `top_left` grows to cover
+    /// the whole block (including any gutters inside it) and the other cells
+    /// are absorbed: iteration skips them and looking them up returns the
+    /// merged cell.
+    /// Returns None, leaving the grid untouched, if the block does not fit in
+    /// the grid or overlaps a block that was already merged.
+    pub fn merge(&mut self, top_left: &CellIndex, span: &CellIndex) -> Option<Cell> {
+        if span.row == 0 || span.col == 0 {
+            return None;
+        }
+        let bottom_right = CellIndex {
+            row: top_left.row + span.row - 1,
+            col: top_left.col + span.col - 1,
+        };
+        let block: Vec<CellIndex> = (top_left.row..=bottom_right.row)
+            .flat_map(|row| {
+                (top_left.col..=bottom_right.col).map(move |col| CellIndex { row, col })
+            })
+            .collect();
+        let is_free = |index: &CellIndex| {
+            index.row < self.num_cells.row
+                && index.col < self.num_cells.col
+                && !self.merged.contains_key(index)
+                && !self.spans.contains_key(index)
+        };
+        if !block.iter().all(is_free) {
+            return None;
+        }
+
+        for index in block.iter().filter(|index| *index != top_left) {
+            self.merged.insert(*index, *top_left);
+        }
+        if *span != (CellIndex { row: 1, col: 1 }) {
+            self.spans.insert(*top_left, *span);
+        }
+        self.get(top_left)
+    }
+
+    /// Looks up the cell at the given (row, column) index. Cells absorbed by
+    /// a merge resolve to the cell that owns the merged block.
+    pub fn get(&self, index: &CellIndex) -> Option<Cell> {
+        self.geometry().get(index)
+    }
+
+    /// Finds the cell containing a point in world coordinates. Returns None
+    /// for points outside of the grid's cells (ex. outside the bounding rect,
+    /// in a margin or in a gutter).
+    pub fn cell_at(&self, point: Vec2) -> Option<Cell> {
+        // Columns go right from the left edge but rows go down from the top
+        // edge while nannou's y axis points up.
+        let col = self.col_tracks.find(point.x - self.inner_rect.left())?;
+        let row = self.row_tracks.find(self.inner_rect.top() - point.y)?;
+        // A point in a gutter finds the cell before the gutter. Checking the
+        // cell's bounds filters those out, except for gutters inside merged cells.
+        self.get(&CellIndex { row, col })
+            .filter(|cell| cell.contains(point))
+    }
+
+    fn geometry(&self) -> Geometry<'_> {
+        Geometry {
+            top_left: self.inner_rect.top_left(),
+            num_cells: self.num_cells,
+            row_tracks: &self.row_tracks,
+            col_tracks: &self.col_tracks,
+            merged: &self.merged,
+            spans: &self.spans,
+        }
+    }
+
+    pub fn num_cells(&self) -> &CellIndex {
+        &self.num_cells
+    }
+
+    pub fn diagonal_length(&self) -> f32 {
+        self.rect.top_left().distance(self.rect.bottom_right())
+    }
+
+    pub fn wh(&self) -> Vec2 {
+        self.rect.wh()
+    }
+
+    pub fn xy(&self) -> Vec2 {
+        self.rect.xy()
+    }
+}
+
+// Everything the cells of a Grid are computed from. It borrows the fields of
+// the grid one by one, which lets Grid::iter_mut compute cells while the
+// grid's values are borrowed mutably.
+#[derive(Clone, Copy)]
+struct Geometry<'a> {
+    top_left: Vec2,
+    num_cells: CellIndex,
+    row_tracks: &'a Tracks,
+    col_tracks: &'a Tracks,
+    merged: &'a HashMap<CellIndex, CellIndex>,
+    spans: &'a HashMap<CellIndex, CellIndex>,
+}
+
+impl<'a> Geometry<'a> {
+    fn get(&self, index: &CellIndex) -> Option<Cell> {
+        // Skip hashing the index for the (common) grids without merges.
+        if self.merged.is_empty() {
+            return self.unmerged_cell(index);
+        }
+        let index = self.merged.get(index).unwrap_or(index);
+        let cell = self.unmerged_cell(index)?;
+        let span = match self.spans.get(index) {
+            Some(span) => span,
+            None => return Some(cell),
+        };
+        // The cell owning a merged block grows to cover the whole block.
+        let last = self.unmerged_cell(&CellIndex {
+            row: index.row + span.row - 1,
+            col: index.col + span.col - 1,
+        })?;
+        let merged_rect = Rect::from_corners(
+            vec2(cell.left(), cell.top()),
+            vec2(last.right(), last.bottom()),
+        );
+        Some(Cell {
+            xy: merged_rect.xy(),
+            wh: merged_rect.wh(),
+            span: *span,
+            ..cell
+        })
+    }
+
+    // The cell at the given index, ignoring merges.
+    fn unmerged_cell(&self, index: &CellIndex) -> Option<Cell> {
+        let row_span = self.row_tracks.span(index.row)?;
+        let col_span = self.col_tracks.span(index.col)?;
+        Some(self.cell(*index, row_span, col_span))
+    }
+
+    // Builds the cell at the given index from the (offset, size) spans of its
+    // row and column.
+    fn cell(
+        &self,
+        index: CellIndex,
+        (row_offset, cell_height): (f32, f32),
+        (col_offset, cell_width): (f32, f32),
+    ) -> Cell {
+        Cell::new(
+            vec2(cell_width, cell_height), // Dimensions
+            vec2(
+                // Anchor from the top left of the grid and
+                // build downwards and to the right.
+                // Note: Nannou grids decrease in x going left
+                // but increase in y going up. Top left is
+                // (-width, height)
+                // Adding half of the size centers the coordinate
+                // in the middle of the cell
+                self.top_left.x + col_offset + (cell_width / 2.0),
+                self.top_left.y - row_offset - (cell_height / 2.0),
+            ),
+            index,
+        )
+    }
+}
+
+pub struct GridIterator<'a> {
+    geometry: Geometry<'a>,
+    curr: CellIndex,
+    // The (offset, size) span of the current row, None past the last row.
+    row_span: Option<(f32, f32)>,
+}
+
+impl<'a> GridIterator<'a> {
+    fn new(geometry: Geometry<'a>) -> Self {
+        GridIterator {
+            geometry,
+            curr: CellIndex { row: 0, col: 0 },
+            row_span: geometry.row_tracks.span(0),
+        }
+    }
+}
+
+impl<'a> Iterator for GridIterator<'a> {
+    type Item = Cell;
+    // Inlined so that loops over the cells in other crates (the sketches)
+    // compute the cells in place instead of calling into this one per cell.
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.curr.col >= self.geometry.num_cells.col {
+                self.curr = CellIndex {
+                    row: self.curr.row + 1,
+                    col: 0,
+                };
+                self.row_span = self.geometry.row_tracks.span(self.curr.row);
+            }
+            let row_span = self.row_span?;
+            let index = self.curr;
+            self.curr.col += 1;
+            if self.geometry.merged.is_empty() {
+                let col_span = self.geometry.col_tracks.span(index.col)?;
+                return Some(self.geometry.cell(index, row_span, col_span));
+            }
+            // Cells absorbed by a merge are drawn as part of the cell
+            // that owns the merged block.
+            if !self.geometry.merged.contains_key(&index) {
+                return self.geometry.get(&index);
+            }
+        }
+    }
+}
+
+/// RowMajorIterator walks the rows of cells of the grids whose cells carry
+/// more than a plain Cell (ex. HexGrid, TriangleGrid).
+pub struct RowMajorIterator<'a, T> {
+    rows: std::slice::Iter<'a, Vec<T>>,
+    current: std::slice::Iter<'a, T>,
+}
+
+impl<'a, T> RowMajorIterator<'a, T> {
+    fn new(rows: &'a [Vec<T>]) -> Self {
+        RowMajorIterator {
+            rows: rows.iter(),
+            current: [].iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for RowMajorIterator<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cell) = self.current.next() {
+                return Some(cell);
+            }
+            self.current = self.rows.next()?.iter();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        fibonacci_weights, golden_ratio_weights, Cell, CellIndex, EdgeMode, Grid, GridLayout,
+    };
+    use nannou::prelude::*;
+
+    impl Grid {
+        fn get_cell_by_index(&self, row: usize, col: usize) -> Option<Cell> {
+            self.get(&CellIndex { row, col })
+        }
+    }
+
+    #[test]
+    fn all_indicies_are_correct() {
+        let grid_cells = CellIndex { row: 5, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 5.0, 5.0);
+        let grid = Grid::new(rect, &grid_cells);
+
+        let mut iter = grid.row_major_iter();
+        for y in 0..grid_cells.row {
+            for x in 0..grid_cells.col {
+                let cell = iter.next();
+                assert!(cell.is_some());
+                assert_eq!(cell.unwrap().index.row, y);
+                assert_eq!(cell.unwrap().index.col, x);
+            }
+        }
+    }
+
+    #[test]
+    fn cell_has_correct_metadata() {
+        let grid_cells = CellIndex { row: 5, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 5.0, 5.0);
+        let grid = Grid::new(rect, &grid_cells);
+
+        let cell_option = grid.get_cell_by_index(0, 2);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.index.row, 0);
+        assert_eq!(cell.index.col, 2);
+        assert_eq!(cell.wh.x, 1.0, "cell width and height should be 1.0");
+        assert_eq!(cell.wh.y, 1.0, "cell width and height should be 1.0");
+        assert_eq!(
+            cell.xy.y, 2.0,
+            "the top row should have a y value of height - cell_height / 2.0"
+        );
+        assert_eq!(
+            cell.xy.x, 0.0,
+            "the second column should have an x value of -width + (cell_height / 2.0) * col",
+        )
+    }
+
+    #[test]
+    fn grid_coordinates_are_correct_when_rect_is_offset() {
+        let grid_cells = CellIndex { row: 5, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(10.0, 10.0, 10.0, 10.0);
+        let grid = Grid::new(rect, &grid_cells);
+
+        let cell_option = grid.get_cell_by_index(0, 0);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.wh.x, 2.0);
+        assert_eq!(cell.wh.y, 2.0);
+        assert_eq!(cell.index.row, 0);
+        assert_eq!(cell.index.col, 0);
+        assert_eq!(cell.xy.x, 6.0);
+        assert_eq!(cell.xy.y, 14.0);
+
+        let cell_option = grid.get_cell_by_index(5, 5);
+        assert!(cell_option.is_none(), "this cell should NOT exist");
+
+        let cell_option = grid.get_cell_by_index(4, 4);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.wh.x, 2.0);
+        assert_eq!(cell.wh.y, 2.0);
+        assert_eq!(cell.index.row, 4);
+        assert_eq!(cell.index.col, 4);
+        assert_eq!(cell.xy.x, 14.0);
+        assert_eq!(cell.xy.y, 6.0);
+    }
+
+    #[test]
+    fn grid_coordinates_are_correct_when_rect_is_not_square() {
+        let grid_cells = CellIndex { row: 10, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 10.0, 100.0);
+        let grid = Grid::new(rect, &grid_cells);
+
+        let cell_option = grid.get_cell_by_index(0, 0);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.wh.x, 2.0);
+        assert_eq!(cell.wh.y, 10.0);
+        assert_eq!(cell.index.row, 0);
+        assert_eq!(cell.index.col, 0);
+        assert_eq!(cell.xy.x, -4.0);
+        assert_eq!(cell.xy.y, 45.0);
+
+        let cell_option = grid.get_cell_by_index(0, 4);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.index.row, 0);
+        assert_eq!(cell.index.col, 4);
+        assert_eq!(cell.xy.x, 4.0);
+        assert_eq!(cell.xy.y, 45.0);
+
+        let cell_option = grid.get_cell_by_index(9, 0);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.index.row, 9);
+        assert_eq!(cell.index.col, 0);
+        assert_eq!(cell.xy.x, -4.0);
+        assert_eq!(cell.xy.y, -45.0);
+
+        let cell_option = grid.get_cell_by_index(9, 4);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.index.row, 9);
+        assert_eq!(cell.index.col, 4);
+        assert_eq!(cell.xy.x, 4.0);
+        assert_eq!(cell.xy.y, -45.0);
+    }
+
+    #[test]
+    fn grid_edge_coordinates_are_correct_using_practical_values() {
+        let grid_cells = CellIndex { row: 30, col: 30 };
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 1500.0, 1500.0);
+        let grid = Grid::new(rect, &grid_cells);
+
+        let cell_option = grid.get_cell_by_index(0, 0);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.wh.x, 50.0);
+        assert_eq!(cell.wh.y, 50.0);
+        assert_eq!(cell.index.row, 0);
+        assert_eq!(cell.index.col, 0);
+        assert_eq!(
+            cell.xy.x - cell.wh.x / 2.0,
+            rect.left(),
+            "cell's left coordinate matches bounding rectangle left"
+        );
+        assert_eq!(
+            cell.xy.y + cell.wh.y / 2.0,
+            rect.top(),
+            "cell's top coordinate matches bounding rectangle top"
+        );
+    }
+
+    #[test]
+    fn weighted_grid_coordinates_are_correct_when_rect_is_offset() {
+        let rect = geom::Rect::from_x_y_w_h(10.0, 10.0, 10.0, 10.0);
+        let layout = GridLayout::weighted(vec![1.0, 3.0], vec![2.0, 2.0, 1.0]);
+        let grid = Grid::with_layout(rect, &layout);
+
+        let cell_option = grid.get_cell_by_index(0, 0);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.wh.x, 4.0);
+        assert_eq!(cell.wh.y, 2.5);
+        assert_eq!(cell.xy.x, 7.0);
+        assert_eq!(cell.xy.y, 13.75);
+
+        let cell_option = grid.get_cell_by_index(2, 3);
+        assert!(cell_option.is_none(), "this cell should NOT exist");
+
+        let cell_option = grid.get_cell_by_index(1, 2);
+        assert!(cell_option.is_some(), "this cell should exist");
+        let cell = cell_option.unwrap();
+        assert_eq!(cell.index.row, 1);
+        assert_eq!(cell.index.col, 2);
+        assert_eq!(cell.wh.x, 2.0);
+        assert_eq!(cell.wh.y, 7.5);
+        assert_eq!(cell.xy.x, 14.0);
+        assert_eq!(cell.xy.y, 8.75);
+        assert_eq!(cell.right(), rect.right());
+        assert_eq!(cell.bottom(), rect.bottom());
+    }
+
+    #[test]
+    fn grid_coordinates_are_correct_with_gutters_and_margin_when_rect_is_not_square() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 10.0, 100.0);
+        let layout = GridLayout::uniform(&CellIndex { row: 3, col: 2 })
+            .gutter(vec2(2.0, 4.0))
+            .margin(1.0);
+        let grid = Grid::with_layout(rect, &layout);
+
+        let cell = grid.get_cell_by_index(0, 0).unwrap();
+        assert_eq!(cell.wh.x, 3.0);
+        assert_eq!(cell.wh.y, 30.0);
+        assert_eq!(cell.xy.x, -2.5);
+        assert_eq!(cell.xy.y, 34.0);
+        assert_eq!(
+            cell.left(),
+            rect.left() + 1.0,
+            "cell's left coordinate is inset from the bounding rectangle by the margin"
+        );
+        assert_eq!(
+            cell.top(),
+            rect.top() - 1.0,
+            "cell's top coordinate is inset from the bounding rectangle by the margin"
+        );
+
+        let cell = grid.get_cell_by_index(1, 1).unwrap();
+        assert_eq!(cell.xy.x, 2.5);
+        assert_eq!(cell.xy.y, 0.0);
+        let left_neighbor = grid.get_cell_by_index(1, 0).unwrap();
+        assert_eq!(cell.left() - left_neighbor.right(), 2.0, "columns are a gutter apart");
+
+        let cell = grid.get_cell_by_index(2, 1).unwrap();
+        assert_eq!(cell.xy.y, -34.0);
+        let top_neighbor = grid.get_cell_by_index(1, 1).unwrap();
+        assert_eq!(top_neighbor.bottom() - cell.top(), 4.0, "rows are a gutter apart");
+    }
+
+    #[test]
+    fn grid_coordinates_follow_fibonacci_and_golden_ratio_weights() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 120.0, 10.0);
+        let layout = GridLayout::weighted(vec![1.0], fibonacci_weights(5));
+        let grid = Grid::with_layout(rect, &layout);
+
+        let widths: Vec<f32> = grid.row_major_iter().map(|cell| cell.wh.x).collect();
+        assert_eq!(widths, vec![10.0, 10.0, 20.0, 30.0, 50.0]);
+        let cell = grid.get_cell_by_index(0, 4).unwrap();
+        assert_eq!(cell.xy.x, 35.0);
+        assert_eq!(cell.right(), rect.right());
+
+        let layout = GridLayout::weighted(golden_ratio_weights(4), vec![1.0]);
+        let grid = Grid::with_layout(rect, &layout);
+        let heights: Vec<f32> = grid.row_major_iter().map(|cell| cell.wh.y).collect();
+        for pair in heights.windows(2) {
+            assert!((pair[0] / pair[1] - 1.618).abs() < 0.001);
+        }
+        let last = grid.get_cell_by_index(3, 0).unwrap();
+        assert!((last.bottom() - rect.bottom()).abs() < 0.001);
+    }
+
+    #[test]
+    fn merged_cells_span_their_block() {
+        let grid_cells = CellIndex { row: 5, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(10.0, 10.0, 10.0, 10.0);
+        let mut grid = Grid::new(rect, &grid_cells);
+        assert_eq!(grid.get_cell_by_index(1, 1).unwrap().wh.x, 2.0);
+
+        let merged = grid.merge(&CellIndex { row: 1, col: 1 }, &CellIndex { row: 2, col: 3 });
+        assert!(merged.is_some(), "the block fits in the grid");
+        let cell = merged.unwrap();
+        assert_eq!(cell.index, CellIndex { row: 1, col: 1 });
+        assert_eq!(cell.span, CellIndex { row: 2, col: 3 });
+        assert_eq!(cell.wh.x, 6.0);
+        assert_eq!(cell.wh.y, 4.0);
+        assert_eq!(cell.xy.x, 10.0);
+        assert_eq!(cell.xy.y, 11.0);
+
+        let absorbed = grid.get_cell_by_index(2, 3).unwrap();
+        assert_eq!(
+            absorbed.index,
+            CellIndex { row: 1, col: 1 },
+            "absorbed cells resolve to the owning cell"
+        );
+        assert_eq!(grid.row_major_iter().count(), 25 - 5);
+        assert!(grid
+            .row_major_iter()
+            .all(|cell| cell.index != CellIndex { row: 2, col: 2 }));
+
+        assert!(
+            grid.merge(&CellIndex { row: 2, col: 0 }, &CellIndex { row: 2, col: 2 })
+                .is_none(),
+            "blocks may not overlap"
+        );
+        assert!(
+            grid.merge(&CellIndex { row: 4, col: 4 }, &CellIndex { row: 2, col: 1 })
+                .is_none(),
+            "blocks must fit in the grid"
+        );
+        assert_eq!(
+            grid.get_cell_by_index(3, 0).unwrap().span,
+            CellIndex { row: 1, col: 1 }
+        );
+    }
+
+    #[test]
+    fn neighbors_are_correct_at_the_edges() {
+        let grid_cells = CellIndex { row: 4, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 5.0, 4.0);
+        let mut grid = Grid::new(rect, &grid_cells);
+
+        let corner = CellIndex { row: 0, col: 0 };
+        let indices = |cells: Vec<Cell>| cells.iter().map(|c| c.index).collect::<Vec<_>>();
+        assert_eq!(
+            indices(grid.neighbors4(&corner)),
+            vec![CellIndex { row: 0, col: 1 }, CellIndex { row: 1, col: 0 }]
+        );
+        assert_eq!(grid.neighbors8(&corner).len(), 3);
+        assert_eq!(grid.neighbors8(&CellIndex { row: 2, col: 2 }).len(), 8);
+        assert_eq!(grid.ring(&CellIndex { row: 2, col: 2 }, 2).len(), 11);
+        assert_eq!(
+            grid.within_radius(&CellIndex { row: 2, col: 2 }, 2).len(),
+            19
+        );
+
+        grid.set_edge_mode(EdgeMode::Toroidal);
+        assert_eq!(
+            indices(grid.neighbors4(&corner)),
+            vec![
+                CellIndex { row: 3, col: 0 },
+                CellIndex { row: 0, col: 4 },
+                CellIndex { row: 0, col: 1 },
+                CellIndex { row: 1, col: 0 },
+            ]
+        );
+        assert_eq!(grid.neighbors8(&corner).len(), 8);
+        assert_eq!(
+            grid.within_radius(&corner, 3).len(),
+            19,
+            "wrapped cells are only returned once"
+        );
+    }
+
+    #[test]
+    fn neighbors_resolve_merged_cells() {
+        let grid_cells = CellIndex { row: 5, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 5.0, 5.0);
+        let mut grid = Grid::new(rect, &grid_cells);
+        grid.merge(&CellIndex { row: 1, col: 1 }, &CellIndex { row: 2, col: 2 });
+
+        let neighbors = grid.neighbors4(&CellIndex { row: 1, col: 0 });
+        assert_eq!(neighbors.len(), 3);
+        assert!(neighbors
+            .iter()
+            .any(|cell| cell.index == CellIndex { row: 1, col: 1 }));
+        let around_merged = grid.neighbors8(&CellIndex { row: 2, col: 2 });
+        assert_eq!(
+            around_merged.len(),
+            12,
+            "the merged cell is not its own neighbor"
+        );
+    }
+
+    #[test]
+    fn cell_at_finds_the_cell_under_a_point() {
+        let grid_cells = CellIndex { row: 10, col: 5 };
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 10.0, 100.0);
+        let grid = Grid::new(rect, &grid_cells);
+
+        let cell = grid.cell_at(vec2(-4.5, 49.0)).unwrap();
+        assert_eq!(cell.index, CellIndex { row: 0, col: 0 });
+        let cell = grid.cell_at(vec2(3.0, -41.0)).unwrap();
+        assert_eq!(cell.index, CellIndex { row: 9, col: 4 });
+        let cell = grid.cell_at(vec2(5.0, -50.0)).unwrap();
+        assert_eq!(
+            cell.index,
+            CellIndex { row: 9, col: 4 },
+            "the bottom right corner belongs to the bottom right cell"
+        );
+        assert!(grid.cell_at(vec2(5.5, 0.0)).is_none());
+        assert!(grid.cell_at(vec2(0.0, 51.0)).is_none());
+        for cell in grid.row_major_iter() {
+            assert_eq!(grid.cell_at(cell.xy).unwrap().index, cell.index);
+        }
+    }
+
+    #[test]
+    fn cell_at_respects_weights_gutters_and_merged_cells() {
+        let rect = geom::Rect::from_x_y_w_h(10.0, 10.0, 10.0, 10.0);
+        let layout = GridLayout::weighted(vec![1.0, 3.0], vec![2.0, 2.0, 1.0]);
+        let grid = Grid::with_layout(rect, &layout);
+        for cell in grid.row_major_iter() {
+            assert_eq!(grid.cell_at(cell.xy).unwrap().index, cell.index);
+        }
+        let cell = grid.cell_at(vec2(14.5, 12.0)).unwrap();
+        assert_eq!(cell.index, CellIndex { row: 1, col: 2 });
+
+        let layout = GridLayout::weighted(vec![1.0, 0.5, 2.0], fibonacci_weights(12))
+            .gutter(vec2(0.5, 0.0));
+        let grid = Grid::with_layout(rect, &layout);
+        for cell in grid.row_major_iter() {
+            assert_eq!(grid.cell_at(cell.xy).unwrap().index, cell.index);
+            let near_top_left = cell.xy + vec2(-0.45, 0.45) * cell.wh;
+            assert_eq!(grid.cell_at(near_top_left).unwrap().index, cell.index);
+        }
+
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 10.0, 100.0);
+        let layout = GridLayout::uniform(&CellIndex { row: 3, col: 2 })
+            .gutter(vec2(2.0, 4.0))
+            .margin(1.0);
+        let mut grid = Grid::with_layout(rect, &layout);
+        assert!(
+            grid.cell_at(vec2(0.0, 30.0)).is_none(),
+            "points in a gutter"
+        );
+        assert!(
+            grid.cell_at(vec2(-2.0, 17.0)).is_none(),
+            "points in a gutter"
+        );
+        assert!(
+            grid.cell_at(vec2(-4.5, 30.0)).is_none(),
+            "points in the margin"
+        );
+        grid.merge(&CellIndex { row: 0, col: 0 }, &CellIndex { row: 1, col: 2 });
+        let cell = grid.cell_at(vec2(0.0, 30.0)).unwrap();
+        assert_eq!(
+            cell.index,
+            CellIndex { row: 0, col: 0 },
+            "gutters inside merged cells belong to the merged cell"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn layouts_need_weights_adding_up_to_more_than_0() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 10.0, 10.0);
+        Grid::with_layout(rect, &GridLayout::weighted(vec![0.0, 0.0], vec![1.0]));
+    }
+}
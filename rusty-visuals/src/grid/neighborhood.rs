@@ -0,0 +1,122 @@
+/// Neighborhood queries on a Grid for cellular automata, mazes and neighbor
+/// aware coloring.
+///
+/// The queries return cells in reading order: row by row from the top left of
+/// the area around the cell, as they sit around it before any wrapping. On a
+/// Toroidal grid the cells wrapped in from the bottom row come first, so this
+/// is only row major order on Bounded grids.
+///
+/// API Examples:
+///
+/// grid.set_edge_mode(EdgeMode::Toroidal);
+/// let alive = grid
+///     .neighbors8(&cell.index)
+///     .iter()
+///     .filter(|neighbor| state[neighbor.index.row][neighbor.index.col])
+///     .count();
+///
+use crate::grid::{Cell, CellIndex, Grid};
+
+/// EdgeMode decides what the neighborhood queries of a Grid do at its edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeMode {
+    /// There is nothing past the edges of the grid.
+    #[default]
+    Bounded,
+    /// The grid wraps around: the last column neighbors the first one and
+    /// the bottom row neighbors the top row.
+    Toroidal,
+}
+
+impl<T> Grid<T> {
+    pub fn set_edge_mode(&mut self, edge_mode: EdgeMode) {
+        self.edge_mode = edge_mode;
+    }
+
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
+
+    /// The cells sharing an edge with the given cell, in reading order.
+    pub fn neighbors4(&self, index: &CellIndex) -> Vec<Cell> {
+        self.cells_around(index, 1, |rows, cols| rows + cols == 1)
+    }
+
+    /// The cells sharing an edge or a corner with the given cell, in reading
+    /// order.
+    pub fn neighbors8(&self, index: &CellIndex) -> Vec<Cell> {
+        self.cells_around(index, 1, |rows, cols| rows.max(cols) == 1)
+    }
+
+    /// The cells exactly `radius` steps (moving like a king in chess) away from
+    /// the given cell, in reading order. A radius of 0 is the cell itself.
+    pub fn ring(&self, index: &CellIndex, radius: usize) -> Vec<Cell> {
+        if radius == 0 {
+            return self.get(index).into_iter().collect();
+        }
+        self.cells_around(index, radius, |rows, cols| rows.max(cols) == radius)
+    }
+
+    /// The cells at most `radius` steps away from the given cell, not
+    /// including the cell itself, in reading order.
+    pub fn within_radius(&self, index: &CellIndex, radius: usize) -> Vec<Cell> {
+        self.cells_around(index, radius, |rows, cols| rows.max(cols) > 0)
+    }
+
+    // Visits every cell up to `radius` rows and columns away from the block
+    // covered by the cell at `index`, row by row from the top left of that
+    // area (reading order), and keeps the ones for which
+    // `include(rows away, columns away)` is true. Merged cells are returned
+    // once and the cell at `index` is never returned.
+    fn cells_around<F>(&self, index: &CellIndex, radius: usize, include: F) -> Vec<Cell>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let center = match self.get(index) {
+            Some(cell) => cell,
+            None => return vec![],
+        };
+        let top = center.index.row as isize;
+        let left = center.index.col as isize;
+        let bottom = top + center.span.row as isize - 1;
+        let right = left + center.span.col as isize - 1;
+        let radius = radius as isize;
+
+        let mut cells: Vec<Cell> = vec![];
+        for row in (top - radius)..=(bottom + radius) {
+            for col in (left - radius)..=(right + radius) {
+                let rows_away = (top - row).max(row - bottom).max(0) as usize;
+                let cols_away = (left - col).max(col - right).max(0) as usize;
+                if !include(rows_away, cols_away) {
+                    continue;
+                }
+                let cell = match self.wrap(row, col).and_then(|i| self.get(&i)) {
+                    Some(cell) => cell,
+                    None => continue,
+                };
+                if cell.index != center.index && cells.iter().all(|c| c.index != cell.index) {
+                    cells.push(cell);
+                }
+            }
+        }
+        cells
+    }
+
+    // Applies the grid's EdgeMode to an index that may be outside the grid.
+    fn wrap(&self, row: isize, col: isize) -> Option<CellIndex> {
+        let rows = self.num_cells.row as isize;
+        let cols = self.num_cells.col as isize;
+        match self.edge_mode {
+            EdgeMode::Bounded if row < 0 || col < 0 || row >= rows || col >= cols => None,
+            EdgeMode::Bounded => Some(CellIndex {
+                row: row as usize,
+                col: col as usize,
+            }),
+            EdgeMode::Toroidal if rows == 0 || cols == 0 => None,
+            EdgeMode::Toroidal => Some(CellIndex {
+                row: row.rem_euclid(rows) as usize,
+                col: col.rem_euclid(cols) as usize,
+            }),
+        }
+    }
+}
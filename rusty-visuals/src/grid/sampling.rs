@@ -0,0 +1,122 @@
+/// Seeded point sets for the grids built from points (ex. VoronoiGrid).
+///
+/// API Examples:
+///
+/// let sites = poisson_disk_points(bounding_rect, 40.0, model.seed);
+/// let grid = VoronoiGrid::new(bounding_rect, &sites);
+///
+use nannou::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// How many candidates are tried around an active point before it is retired.
+// 30 is the value suggested by Bridson.
+const POISSON_ATTEMPTS: usize = 30;
+
+/// `count` points spread uniformly at random over the rect.
+pub fn random_points(bounding_rect: Rect, count: usize, seed: u64) -> Vec<Vec2> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| random_point_in(&bounding_rect, &mut rng))
+        .collect()
+}
+
+/// Random points that are all at least `min_distance` apart and fill the rect
+/// evenly (Poisson disk sampling). Unlike uniformly random points they don't
+/// clump together, which gives Voronoi cells of similar sizes.
+/// See https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph07-poissondisk.pdf
+pub fn poisson_disk_points(bounding_rect: Rect, min_distance: f32, seed: u64) -> Vec<Vec2> {
+    if min_distance <= 0.0 {
+        panic!("poisson disk points must be more than 0 apart");
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    // Every background cell is small enough to hold at most one point.
+    let cell_size = min_distance / std::f32::consts::SQRT_2;
+    let cols = (bounding_rect.w() / cell_size).ceil().max(1.0) as usize;
+    let rows = (bounding_rect.h() / cell_size).ceil().max(1.0) as usize;
+    let background_cell = |point: Vec2| {
+        let col = ((point.x - bounding_rect.left()) / cell_size) as usize;
+        let row = ((point.y - bounding_rect.bottom()) / cell_size) as usize;
+        (row.min(rows - 1), col.min(cols - 1))
+    };
+    let mut background: Vec<Option<usize>> = vec![None; rows * cols];
+
+    let first = random_point_in(&bounding_rect, &mut rng);
+    let mut points = vec![first];
+    let mut active = vec![0];
+    let (row, col) = background_cell(first);
+    background[row * cols + col] = Some(0);
+
+    while !active.is_empty() {
+        let active_index = rng.gen_range(0, active.len());
+        let around = points[active[active_index]];
+        let candidate = (0..POISSON_ATTEMPTS).find_map(|_| {
+            let angle = rng.gen_range(0.0, TAU);
+            let distance = rng.gen_range(min_distance, 2.0 * min_distance);
+            let candidate = around + vec2(angle.cos(), angle.sin()) * distance;
+            if !bounding_rect.contains(candidate) {
+                return None;
+            }
+            // Points closer than min_distance can only be up to two
+            // background cells away.
+            let (row, col) = background_cell(candidate);
+            let is_far_enough = (row.saturating_sub(2)..(row + 3).min(rows)).all(|r| {
+                (col.saturating_sub(2)..(col + 3).min(cols)).all(|c| {
+                    background[r * cols + c]
+                        .is_none_or(|i| points[i].distance(candidate) >= min_distance)
+                })
+            });
+            if is_far_enough {
+                Some(candidate)
+            } else {
+                None
+            }
+        });
+        match candidate {
+            Some(candidate) => {
+                let (row, col) = background_cell(candidate);
+                background[row * cols + col] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+            }
+            None => {
+                active.swap_remove(active_index);
+            }
+        }
+    }
+    points
+}
+
+fn random_point_in(rect: &Rect, rng: &mut StdRng) -> Vec2 {
+    vec2(
+        rng.gen_range(rect.left(), rect.right()),
+        rng.gen_range(rect.bottom(), rect.top()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{poisson_disk_points, random_points};
+    use nannou::prelude::*;
+
+    #[test]
+    fn points_are_seeded_and_inside_the_rect() {
+        let rect = geom::Rect::from_x_y_w_h(10.0, -20.0, 300.0, 200.0);
+        let points = random_points(rect, 50, 3);
+        assert_eq!(points.len(), 50);
+        assert!(points.iter().all(|p| rect.contains(*p)));
+        assert_eq!(points, random_points(rect, 50, 3));
+
+        let points = poisson_disk_points(rect, 20.0, 3);
+        assert!(points.iter().all(|p| rect.contains(*p)));
+        assert_eq!(points, poisson_disk_points(rect, 20.0, 3));
+        for (i, p) in points.iter().enumerate() {
+            for q in points.iter().skip(i + 1) {
+                assert!(p.distance(*q) >= 20.0);
+            }
+        }
+        // Points spaced 40 apart would leave room for more points in between,
+        // so a filled rect has more than (300 / 40) * (200 / 40) of them.
+        assert!(points.len() > 37);
+    }
+}
@@ -0,0 +1,418 @@
+/// Recursive subdivision of a bounding Rect into a tree of uneven cells
+/// (Mondrian style binary splits or quadtrees).
+///
+/// API Examples:
+///
+/// let grid = SubdivisionGrid::builder(bounding_rect)
+///     .max_depth(6)
+///     .split_probability(0.7)
+///     .ratio_range(0.3, 0.7)
+///     .seed(model.seed)
+///     .build();
+/// for node in grid.leaves() {
+///     draw.rect().xy(node.cell.xy).wh(node.cell.wh).color(colorer.color(grid.grid_params(node)));
+/// }
+///
+use crate::colorer::GridParams;
+use crate::grid::{Cell, CellIndex};
+use nannou::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Which way a cell is cut in two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// Cut along a horizontal line into a top and a bottom child.
+    Horizontal,
+    /// Cut along a vertical line into a left and a right child.
+    Vertical,
+}
+
+/// SplitStrategy decides the SplitDirection of every binary split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitStrategy {
+    /// Always cut across the longer side so cells stay close to square.
+    LongestSide,
+    /// Cut horizontally on even depths and vertically on odd depths.
+    Alternating,
+    /// Flip a (seeded) coin.
+    Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Split {
+    pub direction: SplitDirection,
+    // Fraction of the parent given to the first (top or left) child.
+    pub ratio: f32,
+}
+
+pub struct SubdivisionCell {
+    // The cell's index is (depth, position among the cells of that depth)
+    // so that colorers which vary by row vary by level of the tree.
+    pub cell: Cell,
+    // Position of this cell in the grid. Use SubdivisionGrid::get to look up
+    // parents and children.
+    pub id: usize,
+    pub depth: usize,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    // How this cell was split into its children. None for leaves.
+    pub split: Option<Split>,
+}
+
+impl SubdivisionCell {
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+// A SubdivisionGrid is the tree of cells produced by a SubdivisionBuilder,
+// stored in depth first order with the root (the bounding rect) first.
+pub struct SubdivisionGrid {
+    cells: Vec<SubdivisionCell>,
+    rect: Rect,
+    num_cells: CellIndex,
+}
+
+impl SubdivisionGrid {
+    pub fn builder(bounding_rect: Rect) -> SubdivisionBuilder {
+        SubdivisionBuilder::new(bounding_rect)
+    }
+
+    pub fn root(&self) -> &SubdivisionCell {
+        &self.cells[0]
+    }
+
+    pub fn get(&self, id: usize) -> Option<&SubdivisionCell> {
+        self.cells.get(id)
+    }
+
+    pub fn parent(&self, cell: &SubdivisionCell) -> Option<&SubdivisionCell> {
+        cell.parent.and_then(|id| self.get(id))
+    }
+
+    pub fn children<'a>(
+        &'a self,
+        cell: &'a SubdivisionCell,
+    ) -> impl Iterator<Item = &'a SubdivisionCell> + 'a {
+        cell.children.iter().filter_map(move |id| self.get(*id))
+    }
+
+    /// Every cell in the tree, parents before their children.
+    pub fn iter(&self) -> std::slice::Iter<'_, SubdivisionCell> {
+        self.cells.iter()
+    }
+
+    /// The cells that were not split any further. Together they tile the
+    /// bounding rect.
+    pub fn leaves(&self) -> impl Iterator<Item = &SubdivisionCell> {
+        self.cells.iter().filter(|c| c.is_leaf())
+    }
+
+    /// Finds the leaf containing a point in world coordinates by walking down
+    /// from the root. Returns None for points outside of the bounding rect.
+    pub fn cell_at(&self, point: Vec2) -> Option<&SubdivisionCell> {
+        let mut current = self.root();
+        if !current.cell.contains(point) {
+            return None;
+        }
+        while !current.is_leaf() {
+            current = self
+                .children(current)
+                .find(|child| child.cell.contains(point))?;
+        }
+        Some(current)
+    }
+
+    /// GridParams for a cell of the tree. total_num_cells is
+    /// (number of levels, largest number of cells in a level).
+    pub fn grid_params<'a>(&'a self, cell: &'a SubdivisionCell) -> GridParams<'a> {
+        GridParams {
+            cell: &cell.cell,
+            total_num_cells: &self.num_cells,
+        }
+    }
+
+    pub fn num_cells(&self) -> &CellIndex {
+        &self.num_cells
+    }
+
+    /// Depth of the deepest leaf. The root has depth 0.
+    pub fn depth(&self) -> usize {
+        self.num_cells.row - 1
+    }
+
+    pub fn wh(&self) -> Vec2 {
+        self.rect.wh()
+    }
+
+    pub fn xy(&self) -> Vec2 {
+        self.rect.xy()
+    }
+}
+
+pub struct SubdivisionBuilder {
+    rect: Rect,
+    min_depth: usize,
+    max_depth: usize,
+    split_probability: f32,
+    ratio_range: (f32, f32),
+    min_cell_size: f32,
+    strategy: SplitStrategy,
+    quadtree: bool,
+    seed: u64,
+}
+
+impl SubdivisionBuilder {
+    pub fn new(bounding_rect: Rect) -> Self {
+        SubdivisionBuilder {
+            rect: bounding_rect,
+            min_depth: 1,
+            max_depth: 4,
+            split_probability: 0.5,
+            ratio_range: (0.5, 0.5),
+            min_cell_size: 0.0,
+            strategy: SplitStrategy::LongestSide,
+            quadtree: false,
+            seed: 0,
+        }
+    }
+
+    /// Cells shallower than this are always split.
+    pub fn min_depth(mut self, min_depth: usize) -> Self {
+        self.min_depth = min_depth;
+        self
+    }
+
+    /// Cells at this depth are never split.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Chance that a cell between min_depth and max_depth is split.
+    pub fn split_probability(mut self, split_probability: f32) -> Self {
+        self.split_probability = split_probability;
+        self
+    }
+
+    /// Range the split ratio is (uniformly) picked from. 0.5 cuts cells in half.
+    pub fn ratio_range(mut self, min: f32, max: f32) -> Self {
+        self.ratio_range = (min, max);
+        self
+    }
+
+    /// Cells with a width or height smaller than this are never split.
+    pub fn min_cell_size(mut self, min_cell_size: f32) -> Self {
+        self.min_cell_size = min_cell_size;
+        self
+    }
+
+    pub fn strategy(mut self, strategy: SplitStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Split every cell both horizontally and vertically into four children
+    /// instead of in two.
+    pub fn quadtree(mut self) -> Self {
+        self.quadtree = true;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn build(&self) -> SubdivisionGrid {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut cells = vec![];
+        let mut level_counts = vec![];
+        self.subdivide(&mut rng, &mut cells, &mut level_counts, self.rect, 0, None);
+        SubdivisionGrid {
+            cells,
+            rect: self.rect,
+            num_cells: CellIndex {
+                row: level_counts.len(),
+                col: *level_counts.iter().max().unwrap(),
+            },
+        }
+    }
+
+    // Adds the cell for `rect` and then recursively adds its children.
+    // Returns the id of the added cell.
+    fn subdivide(
+        &self,
+        rng: &mut StdRng,
+        cells: &mut Vec<SubdivisionCell>,
+        level_counts: &mut Vec<usize>,
+        rect: Rect,
+        depth: usize,
+        parent: Option<usize>,
+    ) -> usize {
+        if level_counts.len() <= depth {
+            level_counts.push(0);
+        }
+        let index = CellIndex {
+            row: depth,
+            col: level_counts[depth],
+        };
+        level_counts[depth] += 1;
+
+        let id = cells.len();
+        cells.push(SubdivisionCell {
+            cell: Cell::new(rect.wh(), rect.xy(), index),
+            id,
+            depth,
+            parent,
+            children: vec![],
+            split: None,
+        });
+
+        if !self.should_split(rng, &rect, depth) {
+            return id;
+        }
+
+        let ratio = self.pick_ratio(rng);
+        let direction = self.pick_direction(rng, &rect, depth);
+        let halves = split_rect(&rect, direction, ratio);
+        let child_rects = if self.quadtree {
+            let other_ratio = self.pick_ratio(rng);
+            let other_direction = match direction {
+                SplitDirection::Horizontal => SplitDirection::Vertical,
+                SplitDirection::Vertical => SplitDirection::Horizontal,
+            };
+            let (a, b) = split_rect(&halves.0, other_direction, other_ratio);
+            let (c, d) = split_rect(&halves.1, other_direction, other_ratio);
+            vec![a, b, c, d]
+        } else {
+            vec![halves.0, halves.1]
+        };
+
+        let children = child_rects
+            .into_iter()
+            .map(|child| self.subdivide(rng, cells, level_counts, child, depth + 1, Some(id)))
+            .collect();
+        cells[id].children = children;
+        cells[id].split = Some(Split { direction, ratio });
+        id
+    }
+
+    fn should_split(&self, rng: &mut StdRng, rect: &Rect, depth: usize) -> bool {
+        if depth >= self.max_depth || rect.w().min(rect.h()) < self.min_cell_size {
+            return false;
+        }
+        // Always draw from the rng so the shape of the tree only depends on the seed.
+        let roll = rng.gen_range(0.0, 1.0);
+        depth < self.min_depth || roll < self.split_probability
+    }
+
+    fn pick_ratio(&self, rng: &mut StdRng) -> f32 {
+        let (min, max) = self.ratio_range;
+        if min >= max {
+            return min;
+        }
+        rng.gen_range(min, max)
+    }
+
+    fn pick_direction(&self, rng: &mut StdRng, rect: &Rect, depth: usize) -> SplitDirection {
+        match self.strategy {
+            SplitStrategy::LongestSide if rect.h() > rect.w() => SplitDirection::Horizontal,
+            SplitStrategy::LongestSide => SplitDirection::Vertical,
+            SplitStrategy::Alternating if depth.is_multiple_of(2) => SplitDirection::Horizontal,
+            SplitStrategy::Alternating => SplitDirection::Vertical,
+            SplitStrategy::Random if rng.gen_bool(0.5) => SplitDirection::Horizontal,
+            SplitStrategy::Random => SplitDirection::Vertical,
+        }
+    }
+}
+
+// Cuts a rect in two. The first rect returned is the top (horizontal) or
+// left (vertical) one.
+fn split_rect(rect: &Rect, direction: SplitDirection, ratio: f32) -> (Rect, Rect) {
+    match direction {
+        SplitDirection::Horizontal => {
+            let top_h = rect.h() * ratio;
+            let top = Rect::from_corners(
+                rect.top_left(),
+                vec2(rect.right(), rect.top() - top_h),
+            );
+            let bottom = Rect::from_corners(
+                vec2(rect.left(), rect.top() - top_h),
+                rect.bottom_right(),
+            );
+            (top, bottom)
+        }
+        SplitDirection::Vertical => {
+            let left_w = rect.w() * ratio;
+            let left = Rect::from_corners(
+                rect.top_left(),
+                vec2(rect.left() + left_w, rect.bottom()),
+            );
+            let right = Rect::from_corners(
+                vec2(rect.left() + left_w, rect.top()),
+                rect.bottom_right(),
+            );
+            (left, right)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubdivisionGrid;
+    use nannou::prelude::*;
+
+    #[test]
+    fn leaves_tile_the_bounding_rect() {
+        let rect = geom::Rect::from_x_y_w_h(10.0, 10.0, 300.0, 200.0);
+        let grid = SubdivisionGrid::builder(rect)
+            .max_depth(6)
+            .split_probability(0.6)
+            .ratio_range(0.2, 0.8)
+            .seed(7)
+            .build();
+        let area: f32 = grid.leaves().map(|leaf| leaf.cell.wh.x * leaf.cell.wh.y).sum();
+        assert!((area - rect.w() * rect.h()).abs() < 0.1);
+        for leaf in grid.leaves() {
+            assert!(leaf.depth >= 1 && leaf.depth <= 6);
+            let parent = grid.parent(leaf).unwrap();
+            assert_eq!(parent.depth + 1, leaf.depth);
+            assert!(parent.children.contains(&leaf.id));
+        }
+    }
+
+    #[test]
+    fn same_seed_builds_the_same_tree() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let builder = SubdivisionGrid::builder(rect)
+            .max_depth(5)
+            .ratio_range(0.3, 0.7)
+            .quadtree()
+            .seed(42);
+        let a = builder.build();
+        let b = builder.build();
+        assert_eq!(a.iter().count(), b.iter().count());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert_eq!(x.cell.xy, y.cell.xy);
+            assert_eq!(x.cell.wh, y.cell.wh);
+            assert_eq!(x.cell.index, y.cell.index);
+        }
+    }
+
+    #[test]
+    fn cell_at_finds_the_leaf_under_a_point() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = SubdivisionGrid::builder(rect)
+            .max_depth(5)
+            .ratio_range(0.25, 0.75)
+            .seed(3)
+            .build();
+        for leaf in grid.leaves() {
+            let found = grid.cell_at(leaf.cell.xy).unwrap();
+            assert_eq!(found.id, leaf.id);
+        }
+        assert!(grid.cell_at(vec2(60.0, 0.0)).is_none());
+    }
+}
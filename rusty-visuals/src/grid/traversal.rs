@@ -0,0 +1,289 @@
+/// Traversal orders for revealing a Grid cell by cell. Every order visits each
+/// cell once and, like the row major iterator, skips cells absorbed by a merge.
+///
+/// API Examples:
+///
+/// let revealed = (frame.nth() as usize).min(grid.num_cells().row * grid.num_cells().col);
+/// for cell in grid.spiral_in_iter().take(revealed) {...}
+/// for cell in grid.shuffled_iter(seed) {...}
+///
+use crate::grid::{Cell, CellIndex, Grid};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+impl<T> Grid<T> {
+    /// Traverses from the top left of the matrix down each column before
+    /// moving right to the next one.
+    pub fn column_major_iter(&self) -> OrderedGridIterator<'_, T> {
+        let indices = (0..self.num_cells.col)
+            .flat_map(|col| (0..self.num_cells.row).map(move |row| CellIndex { row, col }))
+            .collect();
+        OrderedGridIterator::new(self, indices)
+    }
+
+    /// Traverses the rows like the row major iterator but alternates
+    /// direction: left to right on even rows and right to left on odd rows.
+    pub fn serpentine_iter(&self) -> OrderedGridIterator<'_, T> {
+        let cols = self.num_cells.col;
+        let indices = (0..self.num_cells.row)
+            .flat_map(|row| {
+                (0..cols).map(move |i| CellIndex {
+                    row,
+                    col: if row.is_multiple_of(2) {
+                        i
+                    } else {
+                        cols - 1 - i
+                    },
+                })
+            })
+            .collect();
+        OrderedGridIterator::new(self, indices)
+    }
+
+    /// Traverses clockwise around the edge of the grid, starting in the top
+    /// left, and spirals inwards to the center.
+    pub fn spiral_in_iter(&self) -> OrderedGridIterator<'_, T> {
+        OrderedGridIterator::new(self, spiral_indices(&self.num_cells))
+    }
+
+    /// The spiral in order backwards: starts in the center and spirals
+    /// outwards (counter clockwise) to end in the top left.
+    pub fn spiral_out_iter(&self) -> OrderedGridIterator<'_, T> {
+        let mut indices = spiral_indices(&self.num_cells);
+        indices.reverse();
+        OrderedGridIterator::new(self, indices)
+    }
+
+    /// Traverses the anti-diagonals from the top left to the bottom right.
+    /// Every diagonal goes from its top right cell down to its bottom left cell.
+    pub fn diagonal_iter(&self) -> OrderedGridIterator<'_, T> {
+        let CellIndex {
+            row: rows,
+            col: cols,
+        } = self.num_cells;
+        let num_diagonals = if rows == 0 || cols == 0 {
+            0
+        } else {
+            rows + cols - 1
+        };
+        let indices = (0..num_diagonals)
+            .flat_map(|diagonal| {
+                let first_row = (diagonal + 1).saturating_sub(cols);
+                (first_row..=diagonal.min(rows - 1)).map(move |row| CellIndex {
+                    row,
+                    col: diagonal - row,
+                })
+            })
+            .collect();
+        OrderedGridIterator::new(self, indices)
+    }
+
+    /// Traverses along a Hilbert curve starting in the top left, so that
+    /// consecutive cells are (almost always) neighbors. The curve is laid out on
+    /// the smallest power of two square covering the grid and the points that
+    /// fall outside of the grid are skipped, so grids that are not power of
+    /// two squares jump between a few cells.
+    pub fn hilbert_iter(&self) -> OrderedGridIterator<'_, T> {
+        let side = self
+            .num_cells
+            .row
+            .max(self.num_cells.col)
+            .next_power_of_two();
+        let indices = (0..side * side)
+            .map(|distance| hilbert_index(side, distance))
+            .filter(|index| index.row < self.num_cells.row && index.col < self.num_cells.col)
+            .collect();
+        OrderedGridIterator::new(self, indices)
+    }
+
+    /// Traverses the cells in a random order. The same seed always gives the
+    /// same order.
+    pub fn shuffled_iter(&self, seed: u64) -> OrderedGridIterator<'_, T> {
+        let mut indices: Vec<CellIndex> = (0..self.num_cells.row)
+            .flat_map(|row| (0..self.num_cells.col).map(move |col| CellIndex { row, col }))
+            .collect();
+        indices.shuffle(&mut StdRng::seed_from_u64(seed));
+        OrderedGridIterator::new(self, indices)
+    }
+}
+
+/// OrderedGridIterator visits the cells of a Grid in a precomputed order.
+pub struct OrderedGridIterator<'a, T = ()> {
+    grid: &'a Grid<T>,
+    indices: std::vec::IntoIter<CellIndex>,
+}
+
+impl<'a, T> OrderedGridIterator<'a, T> {
+    fn new(grid: &'a Grid<T>, indices: Vec<CellIndex>) -> Self {
+        OrderedGridIterator {
+            grid,
+            indices: indices.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for OrderedGridIterator<'a, T> {
+    type Item = Cell;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.indices.next()?;
+            // Cells absorbed by a merge are drawn as part of the cell
+            // that owns the merged block.
+            if !self.grid.merged.contains_key(&index) {
+                return self.grid.get(&index);
+            }
+        }
+    }
+}
+
+// Peels the grid like an onion: the top row, the right column, the bottom
+// row backwards and the left column upwards, then repeats one ring further in.
+fn spiral_indices(num_cells: &CellIndex) -> Vec<CellIndex> {
+    let mut indices = Vec::with_capacity(num_cells.row * num_cells.col);
+    let (mut top, mut left) = (0, 0);
+    let (mut bottom, mut right) = (num_cells.row as isize - 1, num_cells.col as isize - 1);
+    let mut push = |row: isize, col: isize| {
+        indices.push(CellIndex {
+            row: row as usize,
+            col: col as usize,
+        })
+    };
+    while top <= bottom && left <= right {
+        (left..=right).for_each(|col| push(top, col));
+        top += 1;
+        (top..=bottom).for_each(|row| push(row, right));
+        right -= 1;
+        if top <= bottom {
+            (left..=right).rev().for_each(|col| push(bottom, col));
+            bottom -= 1;
+        }
+        if left <= right {
+            (top..=bottom).rev().for_each(|row| push(row, left));
+            left += 1;
+        }
+    }
+    indices
+}
+
+// Converts a distance along the Hilbert curve filling a side x side square
+// (side must be a power of two) to the (row, column) it lands on.
+// See https://en.wikipedia.org/wiki/Hilbert_curve#Applications_and_mapping_algorithms
+fn hilbert_index(side: usize, distance: usize) -> CellIndex {
+    let (mut x, mut y) = (0, 0);
+    let mut t = distance;
+    let mut s = 1;
+    while s < side {
+        let rx = 1 & (t / 2);
+        let ry = 1 & (t ^ rx);
+        // Rotate the quadrant so that the curve's sub-curves connect.
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - x;
+                y = s - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        x += s * rx;
+        y += s * ry;
+        t /= 4;
+        s *= 2;
+    }
+    CellIndex { row: y, col: x }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Cell, CellIndex, Grid};
+    use nannou::prelude::*;
+    use std::collections::HashSet;
+
+    fn indices(cells: impl Iterator<Item = Cell>) -> Vec<(usize, usize)> {
+        cells.map(|c| (c.index.row, c.index.col)).collect()
+    }
+
+    #[test]
+    fn orders_visit_every_cell_once() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 60.0);
+        let grid = Grid::new(rect, &CellIndex { row: 3, col: 5 });
+        let orders = vec![
+            indices(grid.column_major_iter()),
+            indices(grid.serpentine_iter()),
+            indices(grid.spiral_in_iter()),
+            indices(grid.spiral_out_iter()),
+            indices(grid.diagonal_iter()),
+            indices(grid.hilbert_iter()),
+            indices(grid.shuffled_iter(7)),
+        ];
+        for order in orders {
+            assert_eq!(order.len(), 15);
+            assert_eq!(order.iter().collect::<HashSet<_>>().len(), 15);
+        }
+    }
+
+    #[test]
+    fn orders_are_correct() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 3.0, 3.0);
+        let grid = Grid::new(rect, &CellIndex { row: 3, col: 3 });
+        assert_eq!(
+            indices(grid.column_major_iter())[..4],
+            [(0, 0), (1, 0), (2, 0), (0, 1)]
+        );
+        assert_eq!(
+            indices(grid.serpentine_iter())[2..5],
+            [(0, 2), (1, 2), (1, 1)]
+        );
+        assert_eq!(
+            indices(grid.spiral_in_iter()),
+            vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2),
+                (2, 1),
+                (2, 0),
+                (1, 0),
+                (1, 1)
+            ]
+        );
+        assert_eq!(indices(grid.spiral_out_iter())[0], (1, 1));
+        assert_eq!(
+            indices(grid.diagonal_iter())[..6],
+            [(0, 0), (0, 1), (1, 0), (0, 2), (1, 1), (2, 0)]
+        );
+        assert_eq!(
+            indices(grid.shuffled_iter(3)),
+            indices(grid.shuffled_iter(3)),
+            "the same seed gives the same order"
+        );
+    }
+
+    #[test]
+    fn hilbert_steps_between_neighbors() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 8.0, 8.0);
+        let grid = Grid::new(rect, &CellIndex { row: 8, col: 8 });
+        let order = indices(grid.hilbert_iter());
+        assert_eq!(order[0], (0, 0));
+        for pair in order.windows(2) {
+            let rows = (pair[0].0 as isize - pair[1].0 as isize).abs();
+            let cols = (pair[0].1 as isize - pair[1].1 as isize).abs();
+            assert_eq!(rows + cols, 1);
+        }
+    }
+
+    #[test]
+    fn orders_skip_merged_cells() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 4.0, 4.0);
+        let mut grid = Grid::new(rect, &CellIndex { row: 4, col: 4 });
+        grid.merge(&CellIndex { row: 1, col: 1 }, &CellIndex { row: 2, col: 2 });
+        assert_eq!(grid.spiral_out_iter().count(), 13);
+        assert_eq!(grid.hilbert_iter().count(), 13);
+        assert_eq!(
+            grid.diagonal_iter()
+                .filter(|cell| cell.span == CellIndex { row: 2, col: 2 })
+                .count(),
+            1
+        );
+    }
+}
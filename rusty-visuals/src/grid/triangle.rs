@@ -0,0 +1,191 @@
+/// Triangular grids made of rows of alternating up and down pointing
+/// equilateral triangles.
+///
+/// API Examples:
+///
+/// let grid = TriangleGrid::new(bounding_rect, &CellIndex { row, col });
+/// for triangle in grid.row_major_iter() {
+///     draw.polygon().points(triangle.points).color(colorer.color(grid.grid_params(triangle)));
+/// }
+///
+use crate::colorer::GridParams;
+use crate::grid::{Cell, CellIndex, RowMajorIterator};
+use nannou::prelude::*;
+
+const HEIGHT_TO_SIDE: f32 = 0.866_025_4; // sqrt(3) / 2
+
+pub struct TriangleCell {
+    // The bounding box of the triangle along with its (row, column) index.
+    pub cell: Cell,
+    // The vertices of the triangle in counter clockwise order.
+    pub points: [Vec2; 3],
+    pub pointing_up: bool,
+}
+
+impl TriangleCell {
+    pub fn centroid(&self) -> Vec2 {
+        (self.points[0] + self.points[1] + self.points[2]) / 3.0
+    }
+}
+
+// A TriangleGrid is a collection of TriangleCells. Every row alternates
+// between up and down pointing triangles that share their slanted edges,
+// starting with an up pointing triangle in the top left.
+pub struct TriangleGrid {
+    cells: Vec<Vec<TriangleCell>>,
+    rect: Rect,
+    num_cells: CellIndex,
+    side: f32,
+}
+
+impl TriangleGrid {
+    /// num_cells: (number of rows, number of triangles per row)
+    /// The triangles are kept equilateral, so the grid is scaled to the
+    /// largest size that fits inside the bounding_rect and then centered in it.
+    pub fn new(bounding_rect: Rect, num_cells: &CellIndex) -> Self {
+        // Neighboring triangles overlap by half a side, so n triangles in a
+        // row span (n + 1) / 2 sides.
+        let lattice_units = vec2(
+            (num_cells.col as f32 + 1.0) / 2.0,
+            num_cells.row as f32 * HEIGHT_TO_SIDE,
+        );
+        let side = (bounding_rect.w() / lattice_units.x).min(bounding_rect.h() / lattice_units.y);
+        let height = side * HEIGHT_TO_SIDE;
+        let lattice = Rect::from_xy_wh(bounding_rect.xy(), lattice_units * side);
+
+        let cells = (0..num_cells.row)
+            .map(|row| {
+                (0..num_cells.col)
+                    .map(|col| {
+                        let left = lattice.left() + col as f32 * side / 2.0;
+                        let top = lattice.top() - row as f32 * height;
+                        let bottom = top - height;
+                        let pointing_up = (row + col).is_multiple_of(2);
+                        let points = if pointing_up {
+                            [
+                                vec2(left, bottom),
+                                vec2(left + side, bottom),
+                                vec2(left + side / 2.0, top),
+                            ]
+                        } else {
+                            [
+                                vec2(left + side / 2.0, bottom),
+                                vec2(left + side, top),
+                                vec2(left, top),
+                            ]
+                        };
+                        TriangleCell {
+                            cell: Cell::new(
+                                vec2(side, height),
+                                vec2(left + side / 2.0, top - height / 2.0),
+                                CellIndex { row, col },
+                            ),
+                            points,
+                            pointing_up,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        TriangleGrid {
+            cells,
+            rect: bounding_rect,
+            num_cells: *num_cells,
+            side,
+        }
+    }
+
+    /// The row major iterator will traverse from the top left of the
+    /// grid down to the bottom right going triangle by triangle in each row.
+    pub fn row_major_iter(&self) -> RowMajorIterator<'_, TriangleCell> {
+        RowMajorIterator::new(&self.cells)
+    }
+
+    pub fn get(&self, index: &CellIndex) -> Option<&TriangleCell> {
+        self.cells.get(index.row).and_then(|r| r.get(index.col))
+    }
+
+    /// The (up to) three triangles that share an edge with the triangle at
+    /// the given index: its left and right neighbors in the row, and the
+    /// triangle below (pointing up) or above (pointing down) it.
+    pub fn neighbors(&self, index: &CellIndex) -> Vec<&TriangleCell> {
+        let triangle = match self.get(index) {
+            Some(triangle) => triangle,
+            None => return vec![],
+        };
+        let mut indices = vec![CellIndex {
+            row: index.row,
+            col: index.col + 1,
+        }];
+        if index.col > 0 {
+            indices.push(CellIndex {
+                row: index.row,
+                col: index.col - 1,
+            });
+        }
+        if triangle.pointing_up {
+            indices.push(CellIndex {
+                row: index.row + 1,
+                col: index.col,
+            });
+        } else if index.row > 0 {
+            indices.push(CellIndex {
+                row: index.row - 1,
+                col: index.col,
+            });
+        }
+        indices.iter().filter_map(|i| self.get(i)).collect()
+    }
+
+    /// GridParams for a triangle in this grid so that any GridColorer can
+    /// color it.
+    pub fn grid_params<'a>(&'a self, triangle: &'a TriangleCell) -> GridParams<'a> {
+        GridParams {
+            cell: &triangle.cell,
+            total_num_cells: &self.num_cells,
+        }
+    }
+
+    pub fn num_cells(&self) -> &CellIndex {
+        &self.num_cells
+    }
+
+    pub fn side_length(&self) -> f32 {
+        self.side
+    }
+
+    pub fn wh(&self) -> Vec2 {
+        self.rect.wh()
+    }
+
+    pub fn xy(&self) -> Vec2 {
+        self.rect.xy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TriangleGrid;
+    use crate::grid::CellIndex;
+    use nannou::prelude::*;
+
+    #[test]
+    fn neighbors_share_an_edge() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = TriangleGrid::new(rect, &CellIndex { row: 4, col: 7 });
+        for triangle in grid.row_major_iter() {
+            for neighbor in grid.neighbors(&triangle.cell.index) {
+                assert_ne!(neighbor.pointing_up, triangle.pointing_up);
+                let shared = triangle
+                    .points
+                    .iter()
+                    .filter(|p| neighbor.points.iter().any(|q| p.distance(*q) < 0.001))
+                    .count();
+                assert_eq!(shared, 2, "neighbors should share exactly two vertices");
+            }
+        }
+        let corner = grid.neighbors(&CellIndex { row: 0, col: 0 });
+        assert_eq!(corner.len(), 2);
+    }
+}
@@ -0,0 +1,304 @@
+/// Voronoi tessellations of a bounding Rect: every site (point) gets the
+/// polygon of the area closer to it than to any other site.
+///
+/// API Examples:
+///
+/// let sites = poisson_disk_points(bounding_rect, 40.0, model.seed);
+/// let grid = VoronoiGrid::new(bounding_rect, &sites);
+/// for cell in grid.iter() {
+///     draw.polygon()
+///         .points(cell.polygon.iter().cloned())
+///         .color(colorer.color(grid.grid_params(cell)));
+/// }
+///
+use crate::colorer::GridParams;
+use crate::grid::{Cell, CellIndex};
+use nannou::prelude::*;
+
+// Sites and polygon vertices closer than this are treated as the same point.
+const EPSILON: f32 = 1e-4;
+
+pub struct VoronoiCell {
+    // The bounding box of the polygon. The index is (0, position of the
+    // cell in the grid).
+    pub cell: Cell,
+    pub site: Vec2,
+    // The corners of the cell in counter clockwise order.
+    pub polygon: Vec<Vec2>,
+    // Positions of the cells sharing an edge with this one.
+    pub neighbors: Vec<usize>,
+}
+
+impl VoronoiCell {
+    /// The center of mass of the polygon. Moving every site to its cell's
+    /// centroid and rebuilding the grid (Lloyd's relaxation) evens out the
+    /// cells.
+    pub fn centroid(&self) -> Vec2 {
+        let mut area = 0.0;
+        let mut centroid = vec2(0.0, 0.0);
+        for (i, a) in self.polygon.iter().enumerate() {
+            let b = self.polygon[(i + 1) % self.polygon.len()];
+            let cross = a.x * b.y - b.x * a.y;
+            area += cross;
+            centroid += (*a + b) * cross;
+        }
+        if area.abs() < EPSILON {
+            return self.site;
+        }
+        centroid / (3.0 * area)
+    }
+}
+
+pub struct VoronoiGrid {
+    cells: Vec<VoronoiCell>,
+    rect: Rect,
+    num_cells: CellIndex,
+}
+
+impl VoronoiGrid {
+    /// Builds one cell per site, clipped to the bounding_rect. Sites outside
+    /// of the bounding_rect and repeated sites are ignored, so the grid may
+    /// have fewer cells than there are sites.
+    pub fn new(bounding_rect: Rect, sites: &[Vec2]) -> Self {
+        let mut unique_sites: Vec<Vec2> = vec![];
+        for site in sites {
+            if bounding_rect.contains(*site)
+                && unique_sites.iter().all(|s| s.distance(*site) > EPSILON)
+            {
+                unique_sites.push(*site);
+            }
+        }
+
+        let cells = unique_sites
+            .iter()
+            .enumerate()
+            .map(|(id, site)| {
+                let (polygon, neighbors) = voronoi_polygon(&bounding_rect, &unique_sites, id);
+                let min = polygon
+                    .iter()
+                    .fold(vec2(f32::MAX, f32::MAX), |m, p| m.min(*p));
+                let max = polygon
+                    .iter()
+                    .fold(vec2(f32::MIN, f32::MIN), |m, p| m.max(*p));
+                let bounds = Rect::from_corners(min, max);
+                VoronoiCell {
+                    cell: Cell::new(bounds.wh(), bounds.xy(), CellIndex { row: 0, col: id }),
+                    site: *site,
+                    polygon,
+                    neighbors,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        VoronoiGrid {
+            num_cells: CellIndex {
+                row: 1,
+                col: cells.len(),
+            },
+            cells,
+            rect: bounding_rect,
+        }
+    }
+
+    /// Every cell in the order of its site.
+    pub fn iter(&self) -> std::slice::Iter<'_, VoronoiCell> {
+        self.cells.iter()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&VoronoiCell> {
+        self.cells.get(id)
+    }
+
+    /// The cells sharing an edge with the cell at the given position.
+    pub fn neighbors(&self, id: usize) -> Vec<&VoronoiCell> {
+        match self.get(id) {
+            Some(cell) => cell.neighbors.iter().filter_map(|n| self.get(*n)).collect(),
+            None => vec![],
+        }
+    }
+
+    /// Finds the cell containing a point in world coordinates, which is the
+    /// cell of the closest site. Returns None for points outside of the
+    /// bounding rect.
+    pub fn cell_at(&self, point: Vec2) -> Option<&VoronoiCell> {
+        if !self.rect.contains(point) {
+            return None;
+        }
+        self.cells.iter().min_by(|a, b| {
+            let a = a.site.distance_squared(point);
+            let b = b.site.distance_squared(point);
+            a.partial_cmp(&b).unwrap()
+        })
+    }
+
+    /// GridParams for a cell in this grid so that any GridColorer can color
+    /// it. total_num_cells is (1, number of cells).
+    pub fn grid_params<'a>(&'a self, cell: &'a VoronoiCell) -> GridParams<'a> {
+        GridParams {
+            cell: &cell.cell,
+            total_num_cells: &self.num_cells,
+        }
+    }
+
+    pub fn num_cells(&self) -> &CellIndex {
+        &self.num_cells
+    }
+
+    pub fn wh(&self) -> Vec2 {
+        self.rect.wh()
+    }
+
+    pub fn xy(&self) -> Vec2 {
+        self.rect.xy()
+    }
+}
+
+// Cuts the bounding rect down to the points closer to sites[id] than to any
+// other site, one perpendicular bisector at a time. Every edge of the polygon
+// remembers the site whose bisector it lies on (None for the edges of the
+// rect) so that the neighbors are the sites left on the final edges.
+fn voronoi_polygon(rect: &Rect, sites: &[Vec2], id: usize) -> (Vec<Vec2>, Vec<usize>) {
+    let site = sites[id];
+    let mut polygon: Vec<(Vec2, Option<usize>)> = vec![
+        (rect.bottom_left(), None),
+        (rect.bottom_right(), None),
+        (rect.top_right(), None),
+        (rect.top_left(), None),
+    ];
+
+    // Closer sites cut off more, and once a site is more than twice as far as
+    // the polygon's farthest corner its bisector can't reach the polygon.
+    let mut others: Vec<usize> = (0..sites.len()).filter(|other| *other != id).collect();
+    others.sort_by(|a, b| {
+        let a = sites[*a].distance_squared(site);
+        let b = sites[*b].distance_squared(site);
+        a.partial_cmp(&b).unwrap()
+    });
+    for other in others {
+        let reach = polygon
+            .iter()
+            .map(|(corner, _)| corner.distance(site))
+            .fold(0.0, f32::max);
+        if sites[other].distance(site) > 2.0 * reach {
+            break;
+        }
+        polygon = clip(&polygon, site, sites[other], other);
+    }
+
+    // Clipping right through a corner leaves edges with no length behind.
+    let len = polygon.len();
+    let edges: Vec<(Vec2, Option<usize>)> = (0..len)
+        .filter(|i| polygon[*i].0.distance(polygon[(i + 1) % len].0) > EPSILON)
+        .map(|i| polygon[i])
+        .collect();
+    let mut neighbors: Vec<usize> = edges.iter().filter_map(|(_, other)| *other).collect();
+    neighbors.sort_unstable();
+    neighbors.dedup();
+    (edges.iter().map(|(corner, _)| *corner).collect(), neighbors)
+}
+
+// Keeps the part of the polygon on `site`'s side of the perpendicular
+// bisector between `site` and `other` (Sutherland-Hodgman with one clip edge).
+fn clip(
+    polygon: &[(Vec2, Option<usize>)],
+    site: Vec2,
+    other: Vec2,
+    other_id: usize,
+) -> Vec<(Vec2, Option<usize>)> {
+    let midpoint = (site + other) / 2.0;
+    let normal = other - site;
+    let is_inside = |point: Vec2| (point - midpoint).dot(normal) <= 0.0;
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, (a, edge)) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()].0;
+        if is_inside(*a) {
+            clipped.push((*a, *edge));
+        }
+        if is_inside(*a) != is_inside(b) {
+            let a_side = (*a - midpoint).dot(normal);
+            let b_side = (b - midpoint).dot(normal);
+            let crossing = *a + (b - *a) * (a_side / (a_side - b_side));
+            // Leaving the kept side starts an edge along the bisector,
+            // entering it continues the current edge.
+            let crossing_edge = if is_inside(*a) { Some(other_id) } else { *edge };
+            clipped.push((crossing, crossing_edge));
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VoronoiGrid;
+    use crate::grid::random_points;
+    use nannou::prelude::*;
+
+    fn area(polygon: &[Vec2]) -> f32 {
+        let twice_area: f32 = (0..polygon.len())
+            .map(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+        twice_area / 2.0
+    }
+
+    #[test]
+    fn cells_tile_the_rect() {
+        let rect = geom::Rect::from_x_y_w_h(50.0, 0.0, 400.0, 300.0);
+        let grid = VoronoiGrid::new(rect, &random_points(rect, 100, 11));
+        assert_eq!(grid.num_cells().col, 100);
+
+        let total_area: f32 = grid.iter().map(|cell| area(&cell.polygon)).sum();
+        assert!((total_area - rect.w() * rect.h()).abs() < 1.0);
+        for cell in grid.iter() {
+            assert!(area(&cell.polygon) > 0.0, "corners are counter clockwise");
+            assert_eq!(grid.cell_at(cell.site).unwrap().cell.index, cell.cell.index);
+            assert_eq!(grid.cell_at(cell.centroid()).unwrap().site, cell.site);
+            for neighbor in grid.neighbors(cell.cell.index.col) {
+                assert!(neighbor.neighbors.contains(&cell.cell.index.col));
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_share_an_edge() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 4.0, 4.0);
+        let sites = vec![
+            vec2(-1.0, -1.0),
+            vec2(1.0, -1.0),
+            vec2(1.0, 1.0),
+            vec2(-1.0, 1.0),
+        ];
+        let grid = VoronoiGrid::new(rect, &sites);
+        let cell = grid.get(2).unwrap();
+        assert_eq!(cell.polygon.len(), 4);
+        assert_eq!(cell.cell.xy, vec2(1.0, 1.0));
+        assert_eq!(cell.cell.wh, vec2(2.0, 2.0));
+        assert_eq!(
+            cell.neighbors,
+            vec![1, 3],
+            "cells touching at a corner are not neighbors"
+        );
+    }
+
+    #[test]
+    fn repeated_and_outside_sites_are_ignored() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 10.0, 10.0);
+        let sites = vec![
+            vec2(-2.0, 0.0),
+            vec2(2.0, 0.0),
+            vec2(-2.0, 0.0),
+            vec2(20.0, 0.0),
+        ];
+        let grid = VoronoiGrid::new(rect, &sites);
+        assert_eq!(grid.iter().count(), 2);
+        assert_eq!(grid.get(0).unwrap().neighbors, vec![1]);
+        assert_eq!(grid.get(1).unwrap().cell.wh, vec2(5.0, 10.0));
+
+        let grid = VoronoiGrid::new(rect, &[vec2(3.0, 3.0)]);
+        assert_eq!(grid.get(0).unwrap().cell.wh, vec2(10.0, 10.0));
+        assert!(grid.get(0).unwrap().neighbors.is_empty());
+    }
+}
@@ -2,9 +2,16 @@ mod color;
 
 pub use color::colorer;
 pub use color::colors;
+pub use color::extraction;
+pub use color::gradient;
+pub use color::harmony;
+pub use color::palettes;
+pub use color::spaces;
 
 pub mod file_utils;
 pub mod force_field;
 pub mod grid;
 pub mod mover;
 pub mod hi_res_capture;
+pub mod truchet;
+pub mod wfc;
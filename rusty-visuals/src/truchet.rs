@@ -0,0 +1,438 @@
+/// Truchet tiles: every cell of a Grid gets the same tile turned by a quarter
+/// turn chosen per cell, and the patterns come from how neighboring tiles
+/// line up.
+///
+/// API Examples:
+///
+/// let truchet = Truchet::builder(TruchetTile::Smith)
+///     .rotation(RotationRule::Noise { scale: 0.005 })
+///     .max_depth(2)
+///     .split_probability(0.3)
+///     .seed(model.seed)
+///     .build(&grid);
+/// truchet.draw(&draw, &TruchetStyle::default().line_weight(8.0));
+///
+use crate::grid::{Cell, Grid};
+use nannou::noise::{NoiseFn, OpenSimplex, Seedable};
+use nannou::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Number of line segments used to draw a quarter circle.
+const ARC_RESOLUTION: usize = 16;
+
+/// The tile set. Every tile is drawn in the unit square and then turned by
+/// its cell's rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruchetTile {
+    /// A line along the diagonal from the top left to the bottom right
+    /// corner. Turning it once gives the other diagonal.
+    Diagonal,
+    /// Truchet's original tile: the square cut along a diagonal with the
+    /// bottom left half filled.
+    Triangle,
+    /// A quarter circle around the top left corner joining the middles of
+    /// the top and left edges.
+    QuarterArc,
+    /// Smith's tile: quarter circles around the top left and the bottom right
+    /// corners, so that the arcs of neighboring tiles join into curves.
+    Smith,
+}
+
+impl TruchetTile {
+    /// Draws the tile filling the rect, turned counter clockwise by
+    /// `rotation` quarter turns.
+    pub fn draw(&self, draw: &Draw, rect: Rect, rotation: u8, style: &TruchetStyle) {
+        if let Some(background) = style.background {
+            draw.rect().xy(rect.xy()).wh(rect.wh()).color(background);
+        }
+        // Maps a point of the unit square centered on the origin to the rect.
+        let to_rect = |point: Vec2| rect.xy() + rotate(point, rotation) * rect.wh();
+        let line = |points: Vec<Vec2>| {
+            draw.polyline()
+                .weight(style.line_weight)
+                .caps_round()
+                .points(points.into_iter().map(to_rect))
+                .color(style.foreground);
+        };
+        match self {
+            TruchetTile::Diagonal => line(vec![vec2(-0.5, 0.5), vec2(0.5, -0.5)]),
+            TruchetTile::Triangle => {
+                let points = vec![vec2(-0.5, 0.5), vec2(0.5, -0.5), vec2(-0.5, -0.5)];
+                draw.polygon()
+                    .points(points.into_iter().map(to_rect))
+                    .color(style.foreground);
+            }
+            TruchetTile::QuarterArc => line(arc(vec2(-0.5, 0.5))),
+            TruchetTile::Smith => {
+                line(arc(vec2(-0.5, 0.5)));
+                line(arc(vec2(0.5, -0.5)));
+            }
+        }
+    }
+
+    /// Number of rotations that look different. Diagonals and Smith tiles
+    /// look the same when turned upside down.
+    pub fn num_rotations(&self) -> u8 {
+        match self {
+            TruchetTile::Diagonal | TruchetTile::Smith => 2,
+            TruchetTile::Triangle | TruchetTile::QuarterArc => 4,
+        }
+    }
+}
+
+// The quarter circle of radius 0.5 around a corner of the unit square that
+// stays inside the square.
+fn arc(corner: Vec2) -> Vec<Vec2> {
+    // The arc is centered on the direction from the corner to the center.
+    let middle = (-corner.y).atan2(-corner.x);
+    (0..=ARC_RESOLUTION)
+        .map(|i| {
+            let angle = middle + (i as f32 / ARC_RESOLUTION as f32 - 0.5) * PI / 2.0;
+            corner + vec2(angle.cos(), angle.sin()) * 0.5
+        })
+        .collect()
+}
+
+fn rotate(point: Vec2, quarter_turns: u8) -> Vec2 {
+    match quarter_turns % 4 {
+        0 => point,
+        1 => vec2(-point.y, point.x),
+        2 => -point,
+        _ => vec2(point.y, -point.x),
+    }
+}
+
+/// RotationRule decides how many quarter turns every tile is turned by.
+#[derive(Clone, Copy)]
+pub enum RotationRule {
+    /// Every tile is turned at random (seeded by the builder's seed).
+    Random,
+    /// Simplex noise at the center of the tile picks the rotation, so nearby
+    /// tiles tend to be turned the same way. Smaller scales give larger
+    /// patches. Every rotation is used by about a quarter of the tiles.
+    Noise { scale: f64 },
+    /// Every tile is turned the same way.
+    Fixed(u8),
+    /// The rotation is computed from the tile's cell (ex. from its index for
+    /// repeating patterns).
+    Custom(fn(&Cell) -> u8),
+}
+
+/// The state of a single tile. A tile that was split in multi scale Truchet
+/// patterns is drawn as its four children instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruchetCell {
+    pub rotation: u8,
+    // The top left, top right, bottom left and bottom right quarters of the
+    // tile, or nothing if the tile was not split.
+    pub children: Vec<TruchetCell>,
+}
+
+/// How the tiles are drawn.
+#[derive(Debug, Clone, Copy)]
+pub struct TruchetStyle {
+    pub foreground: Hsva,
+    // Filled behind every tile when set.
+    pub background: Option<Hsva>,
+    // Stroke weight of the lines and arcs of the largest tiles. Every split
+    // halves it.
+    pub line_weight: f32,
+}
+
+impl Default for TruchetStyle {
+    fn default() -> Self {
+        TruchetStyle {
+            foreground: Hsva::new(0.0, 0.0, 0.0, 1.0),
+            background: None,
+            line_weight: 4.0,
+        }
+    }
+}
+
+impl TruchetStyle {
+    pub fn foreground(mut self, foreground: Hsva) -> Self {
+        self.foreground = foreground;
+        self
+    }
+
+    pub fn background(mut self, background: Hsva) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub fn line_weight(mut self, line_weight: f32) -> Self {
+        self.line_weight = line_weight;
+        self
+    }
+}
+
+/// A Truchet pattern laid over a Grid: the tile and the state of every cell.
+pub struct Truchet {
+    tile: TruchetTile,
+    cells: Grid<TruchetCell>,
+}
+
+impl Truchet {
+    pub fn builder(tile: TruchetTile) -> TruchetBuilder {
+        TruchetBuilder::new(tile)
+    }
+
+    pub fn tile(&self) -> TruchetTile {
+        self.tile
+    }
+
+    /// The state of every tile, laid out like the grid the pattern was built on.
+    pub fn cells(&self) -> &Grid<TruchetCell> {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut Grid<TruchetCell> {
+        &mut self.cells
+    }
+
+    pub fn draw(&self, draw: &Draw, style: &TruchetStyle) {
+        for (cell, truchet_cell) in self.cells.iter() {
+            let rect = Rect::from_xy_wh(cell.xy, cell.wh);
+            self.draw_cell(draw, rect, truchet_cell, style);
+        }
+    }
+
+    fn draw_cell(&self, draw: &Draw, rect: Rect, truchet_cell: &TruchetCell, style: &TruchetStyle) {
+        if truchet_cell.children.is_empty() {
+            self.tile.draw(draw, rect, truchet_cell.rotation, style);
+            return;
+        }
+        let child_style = TruchetStyle {
+            line_weight: style.line_weight / 2.0,
+            ..*style
+        };
+        for (quarter, child) in quarters(rect).iter().zip(truchet_cell.children.iter()) {
+            self.draw_cell(draw, *quarter, child, &child_style);
+        }
+    }
+}
+
+// The top left, top right, bottom left and bottom right quarters of a rect.
+fn quarters(rect: Rect) -> [Rect; 4] {
+    let wh = rect.wh() / 2.0;
+    let offset = wh / 2.0;
+    [
+        Rect::from_xy_wh(rect.xy() + vec2(-offset.x, offset.y), wh),
+        Rect::from_xy_wh(rect.xy() + vec2(offset.x, offset.y), wh),
+        Rect::from_xy_wh(rect.xy() + vec2(-offset.x, -offset.y), wh),
+        Rect::from_xy_wh(rect.xy() + vec2(offset.x, -offset.y), wh),
+    ]
+}
+
+/// TruchetBuilder holds the settings of a Truchet pattern. Tiles are only
+/// split into quarters (multi scale Truchet) when max_depth is above 0.
+pub struct TruchetBuilder {
+    tile: TruchetTile,
+    rotation: RotationRule,
+    max_depth: usize,
+    split_probability: f32,
+    seed: u64,
+}
+
+impl TruchetBuilder {
+    pub fn new(tile: TruchetTile) -> Self {
+        TruchetBuilder {
+            tile,
+            rotation: RotationRule::Random,
+            max_depth: 0,
+            split_probability: 0.5,
+            seed: 0,
+        }
+    }
+
+    pub fn rotation(mut self, rotation: RotationRule) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// How many times a tile may be split into quarters.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Chance that a tile above max_depth is split into quarters.
+    pub fn split_probability(mut self, split_probability: f32) -> Self {
+        self.split_probability = split_probability;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Lays the pattern over the cells of the grid. The same settings and
+    /// grid always give the same pattern.
+    pub fn build<T>(&self, grid: &Grid<T>) -> Truchet {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let noise = OpenSimplex::new().set_seed(self.seed as u32);
+        let mut noise_values = vec![];
+        let mut cells = grid.map(|cell, _| {
+            let rect = Rect::from_xy_wh(cell.xy, cell.wh);
+            self.build_cell(&cell, rect, 0, &mut rng, &noise, &mut noise_values)
+        });
+        if !noise_values.is_empty() {
+            // Noise bunches up around 0, so cutting its range into four equal
+            // parts would hardly ever turn a tile 0 or 3 times. Ranking the
+            // values instead spreads the tiles evenly over the rotations.
+            let mut sorted = noise_values.clone();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mut values = noise_values.into_iter();
+            for (_, truchet_cell) in cells.iter_mut() {
+                rank_rotations(truchet_cell, &mut values, &sorted);
+            }
+        }
+        Truchet {
+            tile: self.tile,
+            cells,
+        }
+    }
+
+    // Tiles that follow the noise get rotation 0 and their noise value is
+    // pushed to noise_values, in the order rank_rotations visits them.
+    fn build_cell(
+        &self,
+        cell: &Cell,
+        rect: Rect,
+        depth: usize,
+        rng: &mut StdRng,
+        noise: &OpenSimplex,
+        noise_values: &mut Vec<f64>,
+    ) -> TruchetCell {
+        let rotation = match self.rotation {
+            // Drawn as a float in [0, num_rotations) the way grid_alignment's
+            // diagonals were, so that its old seeds still give the same
+            // drawings.
+            RotationRule::Random => {
+                rng.gen_range(0.0, self.tile.num_rotations() as f64).floor() as u8
+            }
+            RotationRule::Noise { scale } => {
+                noise_values.push(noise.get([rect.x() as f64 * scale, rect.y() as f64 * scale]));
+                0
+            }
+            RotationRule::Fixed(rotation) => rotation % 4,
+            RotationRule::Custom(rule) => rule(cell) % 4,
+        };
+        let children = if depth < self.max_depth && rng.gen_range(0.0, 1.0) < self.split_probability
+        {
+            quarters(rect)
+                .iter()
+                .map(|quarter| self.build_cell(cell, *quarter, depth + 1, rng, noise, noise_values))
+                .collect()
+        } else {
+            vec![]
+        };
+        TruchetCell { rotation, children }
+    }
+}
+
+// Turns the tile and its children by the quarter of all the noise values
+// (sorted) that their own values fall in.
+fn rank_rotations(
+    truchet_cell: &mut TruchetCell,
+    values: &mut impl Iterator<Item = f64>,
+    sorted: &[f64],
+) {
+    if let Some(value) = values.next() {
+        let rank = sorted.partition_point(|v| *v < value);
+        truchet_cell.rotation = (rank * 4 / sorted.len()) as u8;
+    }
+    for child in truchet_cell.children.iter_mut() {
+        rank_rotations(child, values, sorted);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RotationRule, Truchet, TruchetCell, TruchetTile};
+    use crate::grid::{CellIndex, Grid};
+    use nannou::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn depth(cell: &TruchetCell) -> usize {
+        cell.children
+            .iter()
+            .map(|c| depth(c) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn patterns_are_seeded() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = Grid::new(rect, &CellIndex { row: 10, col: 10 });
+        let builder = Truchet::builder(TruchetTile::Smith)
+            .max_depth(2)
+            .split_probability(0.4)
+            .seed(9);
+        let first: Vec<TruchetCell> = builder
+            .build(&grid)
+            .cells()
+            .iter()
+            .map(|(_, c)| c.clone())
+            .collect();
+        let second: Vec<TruchetCell> = builder
+            .build(&grid)
+            .cells()
+            .iter()
+            .map(|(_, c)| c.clone())
+            .collect();
+        assert_eq!(first, second);
+        assert!(first.iter().all(|c| c.rotation < 4 && depth(c) <= 2));
+        assert!(first.iter().any(|c| !c.children.is_empty()));
+        assert!(first
+            .iter()
+            .all(|c| c.children.is_empty() || c.children.len() == 4));
+    }
+
+    #[test]
+    fn rotation_rules() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = Grid::new(rect, &CellIndex { row: 4, col: 4 });
+        let truchet = Truchet::builder(TruchetTile::Diagonal)
+            .rotation(RotationRule::Fixed(5))
+            .build(&grid);
+        assert!(truchet.cells().iter().all(|(_, c)| c.rotation == 1));
+
+        let checkerboard = |cell: &crate::grid::Cell| ((cell.index.row + cell.index.col) % 2) as u8;
+        let truchet = Truchet::builder(TruchetTile::QuarterArc)
+            .rotation(RotationRule::Custom(checkerboard))
+            .build(&grid);
+        assert_eq!(truchet.cells()[CellIndex { row: 1, col: 2 }].rotation, 1);
+        assert_eq!(truchet.cells()[CellIndex { row: 3, col: 1 }].rotation, 0);
+
+        let truchet = Truchet::builder(TruchetTile::Triangle)
+            .rotation(RotationRule::Noise { scale: 0.01 })
+            .build(&grid);
+        for rotation in 0..4 {
+            let count = truchet
+                .cells()
+                .iter()
+                .filter(|(_, c)| c.rotation == rotation)
+                .count();
+            assert_eq!(count, 4);
+        }
+    }
+
+    #[test]
+    fn random_diagonals_match_grid_alignment() {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        let grid = Grid::new(rect, &CellIndex { row: 5, col: 5 });
+        let truchet = Truchet::builder(TruchetTile::Diagonal)
+            .seed(42)
+            .build(&grid);
+        // The draws grid_alignment's drawings made before they used Truchet.
+        let mut rng = StdRng::seed_from_u64(42);
+        for (_, c) in truchet.cells().iter() {
+            assert_eq!(c.rotation, rng.gen_range(0.0, 2.0).floor() as u8);
+        }
+    }
+}
@@ -0,0 +1,552 @@
+/// Wave Function Collapse: fills every cell of a Grid with a tile so that
+/// neighboring tiles fit together. The tiles and the ways they fit come
+/// either from a tile set with a socket on every side (TiledModel) or from
+/// the small patterns of an example bitmap (OverlappingModel).
+/// See https://github.com/mxgmn/WaveFunctionCollapse
+///
+/// API Examples:
+///
+/// let pipes = TiledModel::new(vec![
+///     Tile::new([0, 0, 0, 0]),
+///     Tile::new([0, 1, 0, 1]),
+///     Tile::new([0, 1, 0, 1]).rotated(1),
+///     Tile::new([1, 1, 1, 1]).weight(0.2),
+/// ]);
+/// let layout: Grid<usize> = pipes.solve(&grid, model.seed).unwrap();
+/// for (cell, tile) in layout.iter() {
+///     draw_pipe(&draw, &cell, pipes.tiles()[*tile]);
+/// }
+///
+/// let example = vec![vec![0, 0, 0, 0], vec![0, 1, 1, 0], vec![0, 1, 1, 0]];
+/// let layout: Grid<u8> = OverlappingModel::new(&example, 2).solve(&grid, model.seed).unwrap();
+///
+use crate::grid::{EdgeMode, Grid};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// How many times the solver starts over after running into a contradiction.
+const MAX_ATTEMPTS: usize = 10;
+
+/// The sides of a tile, clockwise from the top. Up is towards row 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+];
+
+impl Direction {
+    pub fn opposite(self) -> Direction {
+        DIRECTIONS[(self as usize + 2) % 4]
+    }
+
+    // The (row, column) step towards this side.
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+}
+
+/// A tile of a TiledModel. Two tiles can be placed next to each other when
+/// the sockets on their touching sides are equal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    // The sockets in Direction order: up, right, down and left.
+    pub sockets: [u32; 4],
+    // How often the tile is picked compared to the other tiles.
+    pub weight: f64,
+}
+
+impl Tile {
+    pub fn new(sockets: [u32; 4]) -> Self {
+        Tile {
+            sockets,
+            weight: 1.0,
+        }
+    }
+
+    /// How often the tile is picked compared to the other tiles. Panics
+    /// unless the weight is a finite number above 0.
+    pub fn weight(mut self, weight: f64) -> Self {
+        if !(weight > 0.0 && weight.is_finite()) {
+            panic!("tile weights must be finite and more than 0");
+        }
+        self.weight = weight;
+        self
+    }
+
+    pub fn socket(&self, direction: Direction) -> u32 {
+        self.sockets[direction as usize]
+    }
+
+    /// The tile turned counter clockwise by `quarter_turns`, like the
+    /// rotations of Truchet tiles.
+    pub fn rotated(&self, quarter_turns: u8) -> Self {
+        let turns = quarter_turns as usize;
+        Tile {
+            sockets: [
+                self.sockets[turns % 4],
+                self.sockets[(turns + 1) % 4],
+                self.sockets[(turns + 2) % 4],
+                self.sockets[(turns + 3) % 4],
+            ],
+            weight: self.weight,
+        }
+    }
+}
+
+/// The simple tiled model: every cell gets one of the tiles, and the sockets
+/// of neighboring tiles match.
+pub struct TiledModel {
+    tiles: Vec<Tile>,
+}
+
+impl TiledModel {
+    pub fn new(tiles: Vec<Tile>) -> Self {
+        TiledModel { tiles }
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Picks a tile for every cell of the grid and returns their positions in
+    /// tiles(). The grid's EdgeMode decides whether the tiles on opposite
+    /// edges must fit together as well. The same seed always gives the same
+    /// layout. Returns None when every attempt ran into a contradiction.
+    pub fn solve<T>(&self, grid: &Grid<T>, seed: u64) -> Option<Grid<usize>> {
+        let allowed = self
+            .tiles
+            .iter()
+            .map(|tile| {
+                let mut allowed: [Vec<usize>; 4] = Default::default();
+                for direction in DIRECTIONS.iter() {
+                    allowed[*direction as usize] = (0..self.tiles.len())
+                        .filter(|other| {
+                            tile.socket(*direction)
+                                == self.tiles[*other].socket(direction.opposite())
+                        })
+                        .collect();
+                }
+                allowed
+            })
+            .collect();
+        let rules = Rules {
+            weights: self.tiles.iter().map(|tile| tile.weight).collect(),
+            allowed,
+        };
+        let assignment = rules.solve(grid, seed)?;
+        let cols = grid.num_cells().col;
+        Some(grid.map(|cell, _| assignment[cell.index.row * cols + cell.index.col]))
+    }
+}
+
+/// The overlapping model: every pattern_size × pattern_size window of the
+/// output is one of the windows of an example bitmap, so the output looks
+/// locally like the example.
+pub struct OverlappingModel<T> {
+    pattern_size: usize,
+    // Every distinct window of the example, in row major order.
+    patterns: Vec<Vec<T>>,
+    // How often every pattern shows up in the example.
+    weights: Vec<f64>,
+}
+
+impl<T: Clone + PartialEq> OverlappingModel<T> {
+    /// Collects the windows of the example (rows from top to bottom). The
+    /// example wraps around its edges, so every value starts a window.
+    pub fn new(example: &[Vec<T>], pattern_size: usize) -> Self {
+        if pattern_size == 0 {
+            panic!("patterns must be at least 1 value wide");
+        }
+        let rows = example.len();
+        let cols = example.first().map_or(0, |row| row.len());
+        if cols == 0 || example.iter().any(|row| row.len() != cols) {
+            panic!("the example must have rows of the same, non zero, length");
+        }
+
+        let mut patterns: Vec<Vec<T>> = vec![];
+        let mut weights = vec![];
+        for row in 0..rows {
+            for col in 0..cols {
+                let pattern: Vec<T> = (0..pattern_size * pattern_size)
+                    .map(|i| {
+                        let r = (row + i / pattern_size) % rows;
+                        let c = (col + i % pattern_size) % cols;
+                        example[r][c].clone()
+                    })
+                    .collect();
+                match patterns.iter().position(|p| *p == pattern) {
+                    Some(existing) => weights[existing] += 1.0,
+                    None => {
+                        patterns.push(pattern);
+                        weights.push(1.0);
+                    }
+                }
+            }
+        }
+        OverlappingModel {
+            pattern_size,
+            patterns,
+            weights,
+        }
+    }
+
+    pub fn num_patterns(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Fills the grid with values of the example, where every cell gets the
+    /// top left value of the pattern placed on it. The grid's EdgeMode decides
+    /// whether the output wraps around its edges. The same seed always gives
+    /// the same layout. Returns None when every attempt ran into a
+    /// contradiction.
+    pub fn solve<U>(&self, grid: &Grid<U>, seed: u64) -> Option<Grid<T>> {
+        let allowed = self
+            .patterns
+            .iter()
+            .map(|pattern| {
+                let mut allowed: [Vec<usize>; 4] = Default::default();
+                for direction in DIRECTIONS.iter() {
+                    allowed[*direction as usize] = (0..self.patterns.len())
+                        .filter(|other| self.overlaps(pattern, &self.patterns[*other], *direction))
+                        .collect();
+                }
+                allowed
+            })
+            .collect();
+        let rules = Rules {
+            weights: self.weights.clone(),
+            allowed,
+        };
+        let assignment = rules.solve(grid, seed)?;
+        let cols = grid.num_cells().col;
+        Some(grid.map(|cell, _| {
+            self.patterns[assignment[cell.index.row * cols + cell.index.col]][0].clone()
+        }))
+    }
+
+    // Whether `other` placed one step towards `direction` of `pattern` agrees
+    // with it everywhere the two windows overlap.
+    fn overlaps(&self, pattern: &[T], other: &[T], direction: Direction) -> bool {
+        let n = self.pattern_size as isize;
+        let (row_step, col_step) = direction.offset();
+        (0..n).all(|row| {
+            (0..n).all(|col| {
+                let (other_row, other_col) = (row - row_step, col - col_step);
+                if other_row < 0 || other_col < 0 || other_row >= n || other_col >= n {
+                    return true;
+                }
+                pattern[(row * n + col) as usize] == other[(other_row * n + other_col) as usize]
+            })
+        })
+    }
+}
+
+// What both models boil down to: a weight for every tile (or pattern) and the
+// tiles allowed next to it on every side. Tile a allows tile b on one side
+// exactly when b allows a on the opposite side.
+struct Rules {
+    weights: Vec<f64>,
+    allowed: Vec<[Vec<usize>; 4]>,
+}
+
+impl Rules {
+    // The tile of every cell in row major order.
+    fn solve<T>(&self, grid: &Grid<T>, seed: u64) -> Option<Vec<usize>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        // Every restart keeps drawing from the same rng so that the attempts
+        // differ but the result only depends on the seed.
+        (0..MAX_ATTEMPTS).find_map(|_| Wave::new(self, grid).collapse(&mut rng))
+    }
+}
+
+// The tiles still possible in every cell while the solver runs.
+struct Wave<'a> {
+    rules: &'a Rules,
+    rows: usize,
+    cols: usize,
+    toroidal: bool,
+    possible: Vec<Vec<bool>>,
+    num_possible: Vec<usize>,
+    // support[cell][tile][direction] counts the tiles still possible in the
+    // neighbor towards direction that allow the tile in this cell. The tile is
+    // ruled out once one of its counts drops to 0.
+    support: Vec<Vec<[usize; 4]>>,
+    // Ruled out (cell, tile) pairs whose neighbors haven't been updated yet.
+    ruled_out: Vec<(usize, usize)>,
+}
+
+impl<'a> Wave<'a> {
+    fn new<T>(rules: &'a Rules, grid: &Grid<T>) -> Self {
+        let num_cells = grid.num_cells();
+        let len = num_cells.row * num_cells.col;
+        let num_tiles = rules.weights.len();
+        let support: Vec<[usize; 4]> = rules
+            .allowed
+            .iter()
+            .map(|allowed| {
+                let mut counts = [0; 4];
+                for direction in DIRECTIONS.iter() {
+                    counts[*direction as usize] = allowed[*direction as usize].len();
+                }
+                counts
+            })
+            .collect();
+        let mut wave = Wave {
+            rules,
+            rows: num_cells.row,
+            cols: num_cells.col,
+            toroidal: grid.edge_mode() == EdgeMode::Toroidal,
+            possible: vec![vec![true; num_tiles]; len],
+            num_possible: vec![num_tiles; len],
+            support: vec![support; len],
+            ruled_out: vec![],
+        };
+        // Tiles that nothing allows next to them on a side with a neighbor
+        // can't go anywhere. Propagate never visits them since no tile was
+        // ruled out, so they are ruled out before the first observation.
+        for cell in 0..len {
+            for tile in 0..num_tiles {
+                let unsupported = DIRECTIONS.iter().any(|direction| {
+                    wave.neighbor(cell, *direction).is_some()
+                        && wave.support[cell][tile][*direction as usize] == 0
+                });
+                if unsupported && wave.possible[cell][tile] {
+                    wave.rule_out(cell, tile);
+                }
+            }
+        }
+        wave.propagate();
+        wave
+    }
+
+    // Collapses the cell with the fewest choices left to a single tile until
+    // every cell has one. Returns None on a contradiction (a cell with no
+    // tiles left), including one found while the wave was set up.
+    fn collapse(mut self, rng: &mut StdRng) -> Option<Vec<usize>> {
+        if self.num_possible.contains(&0) {
+            return None;
+        }
+        while let Some(cell) = self.least_entropy_cell(rng) {
+            let tile = self.pick(cell, rng);
+            for other in 0..self.possible[cell].len() {
+                if other != tile && self.possible[cell][other] {
+                    self.rule_out(cell, other);
+                }
+            }
+            if !self.propagate() {
+                return None;
+            }
+        }
+        Some(
+            self.possible
+                .iter()
+                .map(|tiles| tiles.iter().position(|possible| *possible).unwrap())
+                .collect(),
+        )
+    }
+
+    // The undecided cell with the lowest Shannon entropy, with a little noise
+    // to break ties. None once every cell is decided.
+    fn least_entropy_cell(&self, rng: &mut StdRng) -> Option<usize> {
+        let mut least: Option<(usize, f64)> = None;
+        for cell in 0..self.possible.len() {
+            if self.num_possible[cell] <= 1 {
+                continue;
+            }
+            let (sum, sum_log) = self
+                .possible_weights(cell)
+                .fold((0.0, 0.0), |(sum, sum_log), (_, w)| {
+                    (sum + w, sum_log + w * w.ln())
+                });
+            let entropy = sum.ln() - sum_log / sum + rng.gen_range(0.0, 1e-6);
+            if least.is_none_or(|(_, least_entropy)| entropy < least_entropy) {
+                least = Some((cell, entropy));
+            }
+        }
+        least.map(|(cell, _)| cell)
+    }
+
+    // A random tile among the ones still possible in the cell, picked by
+    // weight.
+    fn pick(&self, cell: usize, rng: &mut StdRng) -> usize {
+        let total: f64 = self.possible_weights(cell).map(|(_, w)| w).sum();
+        let mut target = rng.gen_range(0.0, total);
+        let mut picked = 0;
+        for (tile, weight) in self.possible_weights(cell) {
+            picked = tile;
+            if target < weight {
+                break;
+            }
+            target -= weight;
+        }
+        picked
+    }
+
+    fn possible_weights(&self, cell: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.rules
+            .weights
+            .iter()
+            .enumerate()
+            .filter(move |(tile, _)| self.possible[cell][*tile])
+            .map(|(tile, weight)| (tile, *weight))
+    }
+
+    fn rule_out(&mut self, cell: usize, tile: usize) {
+        self.possible[cell][tile] = false;
+        self.num_possible[cell] -= 1;
+        self.ruled_out.push((cell, tile));
+    }
+
+    // Rules out the tiles that lost all support in a neighbor until nothing
+    // changes. Returns false on a contradiction.
+    fn propagate(&mut self) -> bool {
+        let rules = self.rules;
+        while let Some((cell, tile)) = self.ruled_out.pop() {
+            for direction in DIRECTIONS.iter() {
+                let neighbor = match self.neighbor(cell, *direction) {
+                    Some(neighbor) => neighbor,
+                    None => continue,
+                };
+                let from = direction.opposite() as usize;
+                for other in rules.allowed[tile][*direction as usize].iter() {
+                    let support = &mut self.support[neighbor][*other][from];
+                    *support -= 1;
+                    if *support == 0 && self.possible[neighbor][*other] {
+                        self.rule_out(neighbor, *other);
+                        if self.num_possible[neighbor] == 0 {
+                            return false;
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn neighbor(&self, cell: usize, direction: Direction) -> Option<usize> {
+        let (row_step, col_step) = direction.offset();
+        let (rows, cols) = (self.rows as isize, self.cols as isize);
+        let mut row = (cell / self.cols) as isize + row_step;
+        let mut col = (cell % self.cols) as isize + col_step;
+        if self.toroidal {
+            row = row.rem_euclid(rows);
+            col = col.rem_euclid(cols);
+        } else if row < 0 || col < 0 || row >= rows || col >= cols {
+            return None;
+        }
+        Some((row * cols + col) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, OverlappingModel, Tile, TiledModel};
+    use crate::grid::{CellIndex, EdgeMode, Grid};
+    use nannou::prelude::*;
+
+    // Every way of connecting the sides of a tile, so any constraint can be
+    // met.
+    fn pipes() -> TiledModel {
+        let mut tiles = vec![
+            Tile::new([0, 0, 0, 0]),
+            Tile::new([0, 1, 0, 1]),
+            Tile::new([0, 1, 0, 1]).rotated(1),
+            Tile::new([1, 1, 1, 1]).weight(0.5),
+        ];
+        for turns in 0..4 {
+            tiles.push(Tile::new([1, 0, 0, 0]).rotated(turns));
+            tiles.push(Tile::new([1, 1, 0, 0]).rotated(turns));
+            tiles.push(Tile::new([1, 1, 1, 0]).rotated(turns).weight(0.2));
+        }
+        TiledModel::new(tiles)
+    }
+
+    fn grid(rows: usize, cols: usize) -> Grid {
+        let rect = geom::Rect::from_x_y_w_h(0.0, 0.0, 100.0, 100.0);
+        Grid::new(
+            rect,
+            &CellIndex {
+                row: rows,
+                col: cols,
+            },
+        )
+    }
+
+    #[test]
+    fn tiled_layouts_fit_together() {
+        let model = pipes();
+        assert_eq!(model.tiles()[2].sockets, [1, 0, 1, 0]);
+        for edge_mode in [EdgeMode::Bounded, EdgeMode::Toroidal].iter() {
+            let mut grid = grid(12, 9);
+            grid.set_edge_mode(*edge_mode);
+            let layout = model.solve(&grid, 4).unwrap();
+            let tile = |row: usize, col: usize| model.tiles()[layout[CellIndex { row, col }]];
+            for row in 0..12 {
+                for col in 0..9 {
+                    if row + 1 < 12 || *edge_mode == EdgeMode::Toroidal {
+                        assert_eq!(
+                            tile(row, col).socket(Direction::Down),
+                            tile((row + 1) % 12, col).socket(Direction::Up)
+                        );
+                    }
+                    if col + 1 < 9 || *edge_mode == EdgeMode::Toroidal {
+                        assert_eq!(
+                            tile(row, col).socket(Direction::Right),
+                            tile(row, (col + 1) % 9).socket(Direction::Left)
+                        );
+                    }
+                }
+            }
+
+            let ids = |layout: &Grid<usize>| layout.iter().map(|(_, id)| *id).collect::<Vec<_>>();
+            assert_eq!(ids(&layout), ids(&model.solve(&grid, 4).unwrap()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn nan_weights_are_rejected() {
+        Tile::new([0, 0, 0, 0]).weight(f64::NAN);
+    }
+
+    #[test]
+    fn impossible_layouts_are_none() {
+        // The top of the tile never fits its bottom, so it can't be stacked.
+        let model = TiledModel::new(vec![Tile::new([0, 2, 1, 2])]);
+        assert!(model.solve(&grid(2, 3), 1).is_none());
+        assert_eq!(model.solve(&grid(1, 3), 1).unwrap().iter().count(), 3);
+    }
+
+    #[test]
+    fn overlapping_layouts_look_like_the_example() {
+        let example = vec![vec!['a', 'b'], vec!['b', 'a']];
+        let model = OverlappingModel::new(&example, 2);
+        assert_eq!(model.num_patterns(), 2);
+        let layout = model.solve(&grid(7, 8), 2).unwrap();
+        for row in 0..7 {
+            for col in 0..8 {
+                let value = layout[CellIndex { row, col }];
+                if row + 1 < 7 {
+                    assert_ne!(value, layout[CellIndex { row: row + 1, col }]);
+                }
+                if col + 1 < 8 {
+                    assert_ne!(value, layout[CellIndex { row, col: col + 1 }]);
+                }
+            }
+        }
+    }
+}